@@ -0,0 +1,54 @@
+//! Attaching additional HTTP headers to the requests of any endpoint.
+
+use std::convert::TryInto;
+
+use http::{HeaderMap, Request};
+use ruma_api::Error;
+
+/// Conversion of an endpoint's request into an `http::Request` with additional headers.
+///
+/// This is implemented for the request of every endpoint, and any other type convertible into an
+/// `http::Request` the same way. The request is first converted using the
+/// `TryFrom<Request> for http::Request<Vec<u8>>` implementation generated by `ruma_api!`, the
+/// extra headers are added afterwards. They replace any headers of the same name set by the
+/// endpoint itself.
+pub trait WithExtraHeaders {
+    /// Converts this request into an `http::Request` and adds the given headers to it.
+    fn into_http_request_with_headers(self, headers: HeaderMap) -> Result<Request<Vec<u8>>, Error>;
+}
+
+impl<T> WithExtraHeaders for T
+where
+    T: TryInto<Request<Vec<u8>>, Error = Error>,
+{
+    fn into_http_request_with_headers(self, headers: HeaderMap) -> Result<Request<Vec<u8>>, Error> {
+        let mut request = self.try_into()?;
+        request.headers_mut().extend(headers);
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use http::{HeaderMap, HeaderValue};
+
+    use super::WithExtraHeaders;
+    use crate::r0::keys::get_keys;
+
+    #[test]
+    fn custom_header_on_get_keys() {
+        let request = get_keys::Request {
+            timeout: None,
+            device_keys: HashMap::new(),
+            token: None,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("1234"));
+
+        let http_request = request.into_http_request_with_headers(headers).unwrap();
+        assert_eq!(http_request.headers()["x-request-id"], "1234");
+        assert_eq!(http_request.uri().path(), "/_matrix/client/r0/keys/query");
+    }
+}
@@ -0,0 +1,98 @@
+//! Attaching additional query parameters to the requests of any endpoint.
+
+use std::convert::TryInto;
+
+use http::{uri::PathAndQuery, Request, Uri};
+use ruma_api::Error;
+use url::form_urlencoded;
+
+/// Conversion of an endpoint's request into an `http::Request` with additional query parameters.
+///
+/// `ruma_api!` can't serialize list fields into the query string, and `query_map` fields can't be
+/// combined with body fields. Parameters that are repeated for each element of a list, like
+/// `server_name=a&server_name=b`, can be appended with this trait instead. The request is first
+/// converted using the `TryFrom<Request> for http::Request<Vec<u8>>` implementation generated by
+/// `ruma_api!`, the extra parameters are added after the endpoint's own ones.
+pub trait WithExtraQuery {
+    /// Converts this request into an `http::Request` and appends the given key-value pairs to its
+    /// query string.
+    fn into_http_request_with_query<I, K, V>(self, pairs: I) -> Result<Request<Vec<u8>>, Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>;
+}
+
+impl<T> WithExtraQuery for T
+where
+    T: TryInto<Request<Vec<u8>>, Error = Error>,
+{
+    fn into_http_request_with_query<I, K, V>(self, pairs: I) -> Result<Request<Vec<u8>>, Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut request = self.try_into()?;
+
+        let mut query =
+            form_urlencoded::Serializer::new(request.uri().query().unwrap_or("").to_string());
+        query.extend_pairs(pairs);
+        let query = query.finish();
+
+        if !query.is_empty() {
+            let path_and_query: PathAndQuery = format!("{}?{}", request.uri().path(), query)
+                .parse()
+                .map_err(http::Error::from)?;
+
+            let mut parts = request.uri().clone().into_parts();
+            parts.path_and_query = Some(path_and_query);
+            *request.uri_mut() = Uri::from_parts(parts).map_err(http::Error::from)?;
+        }
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WithExtraQuery;
+    use crate::r0::{directory::get_public_rooms_filtered, membership::ServerNames};
+
+    fn public_rooms_request(server: Option<&str>) -> get_public_rooms_filtered::Request {
+        get_public_rooms_filtered::Request {
+            server: server.map(ToOwned::to_owned),
+            limit: None,
+            since: None,
+            filter: None,
+            room_network: Default::default(),
+        }
+    }
+
+    #[test]
+    fn append_to_existing_query() {
+        let server_names = ServerNames(vec!["a".to_string(), "b".to_string()]);
+
+        let http_request = public_rooms_request(Some("example.org"))
+            .into_http_request_with_query(server_names)
+            .unwrap();
+        assert_eq!(
+            http_request.uri().query(),
+            Some("server=example.org&server_name=a&server_name=b")
+        );
+    }
+
+    #[test]
+    fn add_query_without_existing_one() {
+        let server_names = ServerNames(vec!["a".to_string(), "b".to_string()]);
+
+        let http_request = public_rooms_request(None)
+            .into_http_request_with_query(server_names)
+            .unwrap();
+        assert_eq!(http_request.uri().path(), "/_matrix/client/r0/publicRooms");
+        assert_eq!(
+            http_request.uri().query(),
+            Some("server_name=a&server_name=b")
+        );
+    }
+}
@@ -8,5 +8,7 @@
     missing_docs
 )]
 
+pub mod extra_headers;
+pub mod extra_query;
 pub mod r0;
 pub mod unversioned;
@@ -28,6 +28,7 @@ pub mod state;
 pub mod sync;
 pub mod tag;
 pub mod thirdparty;
+pub mod to_device;
 pub mod typing;
 pub mod user_directory;
 pub mod voip;
@@ -8,7 +8,11 @@ pub mod request_password_change_token;
 pub mod request_register_token;
 pub mod whoami;
 
+use std::convert::TryFrom;
+
+use http::StatusCode;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// Additional authentication information for the user-interactive authentication API.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -19,3 +23,135 @@ pub struct AuthenticationData {
     /// The value of the session key given by the homeserver.
     session: Option<String>,
 }
+
+/// Information about the authentication required by an endpoint using the user-interactive
+/// authentication API.
+///
+/// This is returned with a 401 status by the first request to such an endpoint.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct UiaaInfo {
+    /// The authentication flows the homeserver accepts for this request.
+    pub flows: Vec<AuthFlow>,
+    /// The stages the client has completed successfully.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub completed: Vec<String>,
+    /// Parameters the client needs for the stages of the flows, keyed by login type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+    /// The value to pass as `session` in the `auth` of the following requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+}
+
+/// A list of stages that together authenticate a request.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuthFlow {
+    /// The login types the client has to complete, in order.
+    pub stages: Vec<String>,
+}
+
+/// The response of an endpoint using the user-interactive authentication API.
+#[derive(Clone, Debug)]
+pub enum UiaaResponse<T> {
+    /// The request was successful.
+    Ok(T),
+    /// The homeserver requires (further) authentication before handling the request.
+    AuthenticationRequired(UiaaInfo),
+}
+
+impl<T> UiaaResponse<T>
+where
+    T: TryFrom<http::Response<Vec<u8>>, Error = ruma_api::Error>,
+{
+    /// Converts an `http::Response` into the response of the endpoint, or the authentication
+    /// information if the homeserver responded with a 401 status and `UiaaInfo`.
+    ///
+    /// Other 401 responses, such as those for an invalid access token, are handled like any other
+    /// unsuccessful response.
+    pub fn try_from_http(response: http::Response<Vec<u8>>) -> Result<Self, ruma_api::Error> {
+        if response.status() == StatusCode::UNAUTHORIZED {
+            if let Ok(info) = serde_json::from_slice(response.body()) {
+                return Ok(UiaaResponse::AuthenticationRequired(info));
+            }
+        }
+
+        T::try_from(response).map(UiaaResponse::Ok)
+    }
+}
+
+impl<T> UiaaResponse<T> {
+    /// Converts this into a `Result`, with the authentication information as the error.
+    pub fn into_result(self) -> Result<T, UiaaInfo> {
+        match self {
+            UiaaResponse::Ok(response) => Ok(response),
+            UiaaResponse::AuthenticationRequired(info) => Err(info),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+    use serde_json::{json, to_vec as to_json_vec, Value as JsonValue};
+
+    use super::{deactivate, UiaaResponse};
+
+    fn http_response(status: StatusCode, body: JsonValue) -> http::Response<Vec<u8>> {
+        http::Response::builder()
+            .status(status)
+            .body(to_json_vec(&body).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn detect_authentication_required() {
+        let response = http_response(
+            StatusCode::UNAUTHORIZED,
+            json!({
+                "flows": [
+                    { "stages": ["m.login.password"] },
+                    { "stages": ["m.login.email.identity", "m.login.recaptcha"] },
+                ],
+                "completed": ["m.login.dummy"],
+                "session": "xxxxxx",
+            }),
+        );
+
+        let info = match UiaaResponse::<deactivate::Response>::try_from_http(response).unwrap() {
+            UiaaResponse::AuthenticationRequired(info) => info,
+            UiaaResponse::Ok(_) => panic!("expected authentication to be required"),
+        };
+        assert_eq!(info.flows.len(), 2);
+        assert_eq!(
+            info.flows[1].stages,
+            ["m.login.email.identity", "m.login.recaptcha"]
+        );
+        assert_eq!(info.completed, ["m.login.dummy"]);
+        assert_eq!(info.session, Some("xxxxxx".to_string()));
+    }
+
+    #[test]
+    fn unauthorized_without_flows_is_an_error() {
+        let response = http_response(
+            StatusCode::UNAUTHORIZED,
+            json!({
+                "errcode": "M_UNKNOWN_TOKEN",
+                "error": "Unrecognised access token",
+            }),
+        );
+
+        assert!(UiaaResponse::<deactivate::Response>::try_from_http(response).is_err());
+    }
+
+    #[test]
+    fn successful_response() {
+        let response = http_response(StatusCode::OK, json!({}));
+
+        assert!(
+            UiaaResponse::<deactivate::Response>::try_from_http(response)
+                .unwrap()
+                .into_result()
+                .is_ok()
+        );
+    }
+}
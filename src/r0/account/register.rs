@@ -5,6 +5,7 @@ use ruma_identifiers::{DeviceId, UserId};
 use serde::{Deserialize, Serialize};
 
 use super::AuthenticationData;
+use crate::unversioned::discover_homeserver::DiscoveryInfo;
 
 ruma_api! {
     metadata {
@@ -72,6 +73,10 @@ ruma_api! {
         ///
         /// Will be the same as the corresponding parameter in the request, if one was specified.
         pub device_id: DeviceId,
+        /// Information to configure the client with, overriding what was discovered with
+        /// `discover_homeserver`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub well_known: Option<DiscoveryInfo>,
     }
 }
 
@@ -86,3 +91,55 @@ pub enum RegistrationKind {
     /// A regular user account
     User,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{json, to_vec as to_json_vec, Value as JsonValue};
+
+    use super::Response;
+
+    fn response(body: JsonValue) -> Response {
+        let http_response = http::Response::builder()
+            .body(to_json_vec(&body).unwrap())
+            .unwrap();
+        Response::try_from(http_response).unwrap()
+    }
+
+    #[test]
+    fn response_with_well_known() {
+        let response = response(json!({
+            "access_token": "token",
+            "home_server": "example.org",
+            "user_id": "@alice:example.org",
+            "device_id": "DEVICE",
+            "well_known": {
+                "m.homeserver": { "base_url": "https://matrix.example.org" },
+                "m.identity_server": { "base_url": "https://id.example.org" },
+            },
+        }));
+
+        let well_known = response.well_known.unwrap();
+        assert_eq!(
+            well_known.homeserver.base_url.as_str(),
+            "https://matrix.example.org/"
+        );
+        assert_eq!(
+            well_known.identity_server.unwrap().base_url.as_str(),
+            "https://id.example.org/"
+        );
+    }
+
+    #[test]
+    fn response_without_well_known() {
+        let response = response(json!({
+            "access_token": "token",
+            "home_server": "example.org",
+            "user_id": "@alice:example.org",
+            "device_id": "DEVICE",
+        }));
+
+        assert!(response.well_known.is_none());
+    }
+}
@@ -34,12 +34,57 @@ pub struct Capabilities {
     #[serde(rename = "m.room_versions", skip_serializing_if = "Option::is_none")]
     pub room_versions: Option<RoomVersionsCapability>,
 
+    /// Capability to indicate if the user can change their display name.
+    #[serde(rename = "m.set_displayname", skip_serializing_if = "Option::is_none")]
+    pub set_displayname: Option<SetDisplayNameCapability>,
+
+    /// Capability to indicate if the user can change their avatar.
+    #[serde(rename = "m.set_avatar_url", skip_serializing_if = "Option::is_none")]
+    pub set_avatar_url: Option<SetAvatarUrlCapability>,
+
+    /// Capability to indicate if the user can change the third party identifiers associated with
+    /// their account.
+    #[serde(rename = "m.3pid_changes", skip_serializing_if = "Option::is_none")]
+    pub thirdparty_id_changes: Option<ThirdPartyIdChangesCapability>,
+
     /// Any other custom capabilities that the server supports outside of the specification,
     /// labeled using the Java package naming convention and stored as arbitrary JSON values.
     #[serde(flatten)]
     pub custom_capabilities: HashMap<String, Value>,
 }
 
+impl Capabilities {
+    /// Whether the user can change their display name.
+    ///
+    /// Defaults to `true` if the server doesn't advertise the `m.set_displayname` capability.
+    pub fn can_set_displayname(&self) -> bool {
+        match self.set_displayname {
+            Some(capability) => capability.enabled,
+            None => true,
+        }
+    }
+
+    /// Whether the user can change their avatar.
+    ///
+    /// Defaults to `true` if the server doesn't advertise the `m.set_avatar_url` capability.
+    pub fn can_set_avatar_url(&self) -> bool {
+        match self.set_avatar_url {
+            Some(capability) => capability.enabled,
+            None => true,
+        }
+    }
+
+    /// Whether the user can change the third party identifiers associated with their account.
+    ///
+    /// Defaults to `true` if the server doesn't advertise the `m.3pid_changes` capability.
+    pub fn can_change_thirdparty_ids(&self) -> bool {
+        match self.thirdparty_id_changes {
+            Some(capability) => capability.enabled,
+            None => true,
+        }
+    }
+}
+
 /// Information about the m.change_password capability
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct ChangePasswordCapability {
@@ -47,6 +92,28 @@ pub struct ChangePasswordCapability {
     pub enabled: bool,
 }
 
+/// Information about the m.set_displayname capability
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SetDisplayNameCapability {
+    /// True if the user can change their display name, false otherwise.
+    pub enabled: bool,
+}
+
+/// Information about the m.set_avatar_url capability
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SetAvatarUrlCapability {
+    /// True if the user can change their avatar, false otherwise.
+    pub enabled: bool,
+}
+
+/// Information about the m.3pid_changes capability
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ThirdPartyIdChangesCapability {
+    /// True if the user can change the third party identifiers associated with their account,
+    /// false otherwise.
+    pub enabled: bool,
+}
+
 /// Information about the m.room_versions capability
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RoomVersionsCapability {
@@ -68,3 +135,35 @@ pub enum RoomVersionStability {
     #[serde(rename = "unstable")]
     Unstable,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json};
+
+    use super::Capabilities;
+
+    #[test]
+    fn deserialize_profile_capabilities() {
+        let capabilities = from_json_value::<Capabilities>(json!({
+            "m.set_displayname": { "enabled": false },
+            "m.set_avatar_url": { "enabled": true },
+            "m.3pid_changes": { "enabled": false },
+        }))
+        .unwrap();
+
+        assert!(!capabilities.can_set_displayname());
+        assert!(capabilities.can_set_avatar_url());
+        assert!(!capabilities.can_change_thirdparty_ids());
+        assert!(capabilities.custom_capabilities.is_empty());
+    }
+
+    #[test]
+    fn missing_profile_capabilities_default_to_enabled() {
+        let capabilities = from_json_value::<Capabilities>(json!({})).unwrap();
+
+        assert!(capabilities.set_displayname.is_none());
+        assert!(capabilities.can_set_displayname());
+        assert!(capabilities.can_set_avatar_url());
+        assert!(capabilities.can_change_thirdparty_ids());
+    }
+}
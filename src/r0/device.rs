@@ -1,5 +1,7 @@
 //! Endpoints for managing devices.
 
+use std::fmt::{Debug, Display, Error as FmtError, Formatter};
+
 use js_int::UInt;
 use ruma_identifiers::DeviceId;
 use serde::{Deserialize, Serialize};
@@ -11,7 +13,10 @@ pub mod get_devices;
 pub mod update_device;
 
 /// Information about a registered device.
-#[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
+///
+/// The `Debug` output of this type does not include the IP address the device was last seen at,
+/// unless the `debug-pii` feature is enabled.
+#[derive(Clone, Deserialize, Hash, PartialEq, Serialize)]
 pub struct Device {
     /// Device ID
     pub device_id: DeviceId,
@@ -22,3 +27,88 @@ pub struct Device {
     /// Unix timestamp that the session was last active.
     pub last_seen: Option<UInt>,
 }
+
+impl Debug for Device {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        let ip = match &self.ip {
+            Some(ip) if cfg!(feature = "debug-pii") => Some(ip.as_str()),
+            Some(_) => Some("<redacted>"),
+            None => None,
+        };
+
+        f.debug_struct("Device")
+            .field("device_id", &self.device_id)
+            .field("display_name", &self.display_name)
+            .field("ip", &ip)
+            .field("last_seen", &self.last_seen)
+            .finish()
+    }
+}
+
+/// A conventional display name for a device, in the form "App on Platform".
+///
+/// Can be converted into a `String` for use as the `initial_device_display_name` of a login or
+/// register request, or the `display_name` of an `update_device` request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceDisplayName {
+    app: String,
+    platform: String,
+}
+
+impl DeviceDisplayName {
+    /// Creates a display name for the given client application running on the given platform.
+    pub fn new(app: impl Into<String>, platform: impl Into<String>) -> Self {
+        Self {
+            app: app.into(),
+            platform: platform.into(),
+        }
+    }
+}
+
+impl Display for DeviceDisplayName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "{} on {}", self.app, self.platform)
+    }
+}
+
+impl From<DeviceDisplayName> for String {
+    fn from(display_name: DeviceDisplayName) -> Self {
+        display_name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Device, DeviceDisplayName};
+
+    fn device() -> Device {
+        Device {
+            device_id: "DEVICE".to_string(),
+            display_name: Some("Phone".to_string()),
+            ip: Some("203.0.113.7".to_string()),
+            last_seen: None,
+        }
+    }
+
+    #[cfg(not(feature = "debug-pii"))]
+    #[test]
+    fn debug_output_redacts_ip() {
+        let debug = format!("{:?}", device());
+        assert!(!debug.contains("203.0.113.7"));
+        assert!(debug.contains("<redacted>"));
+    }
+
+    #[cfg(feature = "debug-pii")]
+    #[test]
+    fn debug_output_includes_ip() {
+        assert!(format!("{:?}", device()).contains("203.0.113.7"));
+    }
+
+    #[test]
+    fn display_name() {
+        let display_name = DeviceDisplayName::new("Element", "Android");
+
+        assert_eq!(display_name.to_string(), "Element on Android");
+        assert_eq!(String::from(display_name), "Element on Android");
+    }
+}
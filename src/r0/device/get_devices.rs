@@ -1,5 +1,7 @@
 //! [GET /_matrix/client/r0/devices](https://matrix.org/docs/spec/client_server/r0.6.0#get-matrix-client-r0-devices)
 
+use std::cmp::Reverse;
+
 use super::Device;
 use ruma_api::ruma_api;
 
@@ -19,3 +21,45 @@ ruma_api! {
         devices: Vec<Device>,
     }
 }
+
+impl Response {
+    /// The devices of the user, sorted by when they were last seen, most recent first.
+    ///
+    /// Devices without a last seen timestamp come last.
+    pub fn by_last_seen(&self) -> Vec<&Device> {
+        let mut devices: Vec<_> = self.devices.iter().collect();
+        devices.sort_by_key(|device| Reverse(device.last_seen));
+        devices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::Response;
+
+    #[test]
+    fn sort_by_last_seen() {
+        let body = json!({
+            "devices": [
+                { "device_id": "OLD", "last_seen": 100 },
+                { "device_id": "UNSEEN" },
+                { "device_id": "NEW", "last_seen": 300 },
+            ]
+        });
+        let http_response = http::Response::builder()
+            .body(to_json_vec(&body).unwrap())
+            .unwrap();
+
+        let response = Response::try_from(http_response).unwrap();
+        let device_ids: Vec<_> = response
+            .by_last_seen()
+            .into_iter()
+            .map(|device| device.device_id.as_str())
+            .collect();
+        assert_eq!(device_ids, ["NEW", "OLD", "UNSEEN"]);
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod get_public_rooms;
 pub mod get_public_rooms_filtered;
+pub mod get_room_visibility;
 
 use js_int::UInt;
 use ruma_identifiers::{RoomAliasId, RoomId};
@@ -2,7 +2,7 @@
 
 use js_int::UInt;
 use ruma_api::ruma_api;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 
 use super::PublicRoomsChunk;
 
@@ -22,16 +22,19 @@ ruma_api! {
         /// `None` means the server this request is sent to.
         #[serde(skip_serializing_if = "Option::is_none")]
         #[ruma_api(query)]
-        server: Option<String>,
+        pub server: Option<String>,
         /// Limit for the number of results to return.
         #[serde(skip_serializing_if = "Option::is_none")]
-        limit: Option<UInt>,
+        pub limit: Option<UInt>,
         /// Pagination token from a previous request.
         #[serde(skip_serializing_if = "Option::is_none")]
-        since: Option<String>,
+        pub since: Option<String>,
         /// Filter to apply to the results.
         #[serde(skip_serializing_if = "Option::is_none")]
-        filter: Option<Filter>,
+        pub filter: Option<Filter>,
+        /// Which networks to fetch the public rooms of.
+        #[serde(flatten)]
+        pub room_network: NetworkSelection,
     }
 
     response {
@@ -51,5 +54,121 @@ ruma_api! {
 pub struct Filter {
     /// A string to search for in the room metadata, e.g. name, topic, canonical alias etc.
     #[serde(skip_serializing_if = "Option::is_none")]
-    generic_search_term: Option<String>,
+    pub generic_search_term: Option<String>,
+}
+
+/// The networks to return public rooms from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkSelection {
+    /// Return rooms from all networks, the Matrix network and every third-party network known to
+    /// the homeserver.
+    All,
+    /// Return rooms from the Matrix network only.
+    Matrix,
+    /// Return rooms from the third-party network with the given instance ID only.
+    ThirdParty(String),
+}
+
+impl Default for NetworkSelection {
+    /// Homeservers only return rooms from the Matrix network unless specified otherwise.
+    fn default() -> Self {
+        NetworkSelection::Matrix
+    }
+}
+
+impl Serialize for NetworkSelection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            NetworkSelection::All => map.serialize_entry("include_all_networks", &true)?,
+            NetworkSelection::Matrix => {}
+            NetworkSelection::ThirdParty(instance_id) => {
+                map.serialize_entry("third_party_instance_id", instance_id)?
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for NetworkSelection {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct NetworkFields {
+            #[serde(default)]
+            include_all_networks: bool,
+            third_party_instance_id: Option<String>,
+        }
+
+        let fields = NetworkFields::deserialize(deserializer)?;
+        match (fields.include_all_networks, fields.third_party_instance_id) {
+            (true, Some(_)) => Err(D::Error::custom(
+                "`third_party_instance_id` can't be set if `include_all_networks` is true",
+            )),
+            (true, None) => Ok(NetworkSelection::All),
+            (false, Some(instance_id)) => Ok(NetworkSelection::ThirdParty(instance_id)),
+            (false, None) => Ok(NetworkSelection::Matrix),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{Filter, NetworkSelection, Request};
+
+    fn request(room_network: NetworkSelection) -> Request {
+        Request {
+            server: None,
+            limit: None,
+            since: None,
+            filter: Some(Filter {
+                generic_search_term: Some("rust".to_string()),
+            }),
+            room_network,
+        }
+    }
+
+    fn body(request: Request) -> JsonValue {
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+        from_json_slice(http_request.body()).unwrap()
+    }
+
+    #[test]
+    fn serialize_all_networks() {
+        assert_eq!(
+            body(request(NetworkSelection::All)),
+            json!({
+                "filter": { "generic_search_term": "rust" },
+                "include_all_networks": true,
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_matrix_network() {
+        assert_eq!(
+            body(request(NetworkSelection::Matrix)),
+            json!({ "filter": { "generic_search_term": "rust" } })
+        );
+    }
+
+    #[test]
+    fn serialize_third_party_network() {
+        assert_eq!(
+            body(request(NetworkSelection::ThirdParty("irc".to_string()))),
+            json!({
+                "filter": { "generic_search_term": "rust" },
+                "third_party_instance_id": "irc",
+            })
+        );
+    }
 }
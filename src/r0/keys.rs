@@ -3,15 +3,19 @@
 use std::{
     collections::HashMap,
     convert::TryFrom,
+    error::Error,
     fmt::{Debug, Display, Error as FmtError, Formatter},
+    str::FromStr,
 };
 
+use js_int::UInt;
 use ruma_events::Algorithm;
 use ruma_identifiers::{DeviceId, UserId};
 use serde::{
     de::{self, Unexpected},
     Deserialize, Deserializer, Serialize, Serializer,
 };
+use serde_json::Value;
 
 pub mod claim_keys;
 pub mod get_key_changes;
@@ -46,66 +50,105 @@ impl Display for KeyAlgorithm {
     }
 }
 
-impl TryFrom<&'_ str> for KeyAlgorithm {
-    type Error = &'static str;
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
+impl FromStr for KeyAlgorithm {
+    type Err = ParseKeyIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "ed25519" => Ok(KeyAlgorithm::Ed25519),
             "curve25519" => Ok(KeyAlgorithm::Curve25519),
             "signed_curve25519" => Ok(KeyAlgorithm::SignedCurve25519),
-            _ => Err("Unknown algorithm"),
+            _ => Err(ParseKeyIdError::UnknownAlgorithm(s.to_string())),
         }
     }
 }
 
+impl TryFrom<&'_ str> for KeyAlgorithm {
+    type Error = ParseKeyIdError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 /// A key algorithm and a device id, combined with a ':'
+///
+/// Only the first ':' separates the algorithm from the device id, the device id itself may
+/// contain more of them.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct AlgorithmAndDeviceId(pub KeyAlgorithm, pub DeviceId);
 
+impl Display for AlgorithmAndDeviceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "{}:{}", self.0, self.1)
+    }
+}
+
+impl FromStr for AlgorithmAndDeviceId {
+    type Err = ParseKeyIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let separator = s.find(':').ok_or(ParseKeyIdError::MissingSeparator)?;
+        let algorithm = s[..separator].parse()?;
+        let device_id = s[separator + 1..].to_string();
+
+        Ok(AlgorithmAndDeviceId(algorithm, device_id))
+    }
+}
+
 impl Serialize for AlgorithmAndDeviceId {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let s = format!("{}:{}", self.0, self.1);
-        serializer.serialize_str(&s)
+        serializer.serialize_str(&self.to_string())
     }
 }
 
 impl<'de> Deserialize<'de> for AlgorithmAndDeviceId {
-    #[allow(clippy::comparison_chain)]
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let value = String::deserialize(deserializer)?;
-        let parts = value.split(':').collect::<Vec<_>>();
 
         const EXPECTED: &str = "a string composed of an algorithm and a device id separated by ':'";
 
-        if parts.len() < 2 {
-            return Err(de::Error::invalid_type(
+        value.parse().map_err(|error| match error {
+            ParseKeyIdError::MissingSeparator => de::Error::invalid_type(
                 Unexpected::Other("string without a ':' separator"),
                 &EXPECTED,
-            ));
-        } else if parts.len() > 2 {
-            return Err(de::Error::invalid_type(
-                Unexpected::Other("string with more than one ':' separator"),
-                &EXPECTED,
-            ));
-        }
+            ),
+            ParseKeyIdError::UnknownAlgorithm(algorithm) => {
+                de::Error::invalid_value(Unexpected::Str(&algorithm), &"valid key algorithm")
+            }
+        })
+    }
+}
 
-        let algorithm_result = KeyAlgorithm::try_from(parts[0]);
-        match algorithm_result {
-            Ok(algorithm) => Ok(AlgorithmAndDeviceId(algorithm, parts[1].to_string())),
-            Err(_) => Err(de::Error::invalid_value(
-                Unexpected::Str(parts[0]),
-                &"valid key algorithm",
-            )),
+/// An error when parsing a `KeyAlgorithm` or an `AlgorithmAndDeviceId` from a string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseKeyIdError {
+    /// The algorithm is not one of the key algorithms in the specification.
+    UnknownAlgorithm(String),
+    /// There is no ':' separating the algorithm from the device id.
+    MissingSeparator,
+}
+
+impl Display for ParseKeyIdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            ParseKeyIdError::UnknownAlgorithm(algorithm) => {
+                write!(f, "unknown key algorithm `{}`", algorithm)
+            }
+            ParseKeyIdError::MissingSeparator => {
+                write!(f, "missing ':' between the algorithm and the device id")
+            }
         }
     }
 }
 
+impl Error for ParseKeyIdError {}
+
 /// Identity keys for a device.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeviceKeys {
@@ -139,6 +182,11 @@ pub struct SignedKey {
     pub key: String,
     /// Signatures for the key object.
     pub signatures: HashMap<UserId, HashMap<AlgorithmAndDeviceId, String>>,
+    /// Whether this is a fallback key.
+    ///
+    /// The flag is part of the signed object, so it has to be set before signing the key.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fallback: bool,
 }
 
 /// A one-time public key for "pre-key" messages.
@@ -150,3 +198,268 @@ pub enum OneTimeKey {
     /// A string-valued key, for the Ed25519 and Curve25519 algorithms.
     Key(String),
 }
+
+/// For each key algorithm, the number of unclaimed one-time keys of that type currently held on
+/// the server for a device.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OneTimeKeyCounts(pub HashMap<KeyAlgorithm, UInt>);
+
+impl OneTimeKeyCounts {
+    /// The number of unclaimed one-time keys for the given algorithm.
+    ///
+    /// Algorithms the server didn't report a count for have no keys left.
+    pub fn get(&self, algorithm: KeyAlgorithm) -> UInt {
+        self.0
+            .get(&algorithm)
+            .copied()
+            .unwrap_or_else(|| UInt::from(0u32))
+    }
+
+    /// The number of unclaimed `signed_curve25519` one-time keys.
+    pub fn signed_curve25519(&self) -> UInt {
+        self.get(KeyAlgorithm::SignedCurve25519)
+    }
+
+    /// Whether the server didn't report a count for any algorithm.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Updates these counts with the ones of a newer response, e.g. counts of an `upload_keys`
+    /// response with the counts of a later sync.
+    ///
+    /// The newer count replaces the current one for each algorithm it has a count for, counts for
+    /// other algorithms are kept.
+    pub fn merge(&mut self, newer: &OneTimeKeyCounts) {
+        self.0.extend(
+            newer
+                .0
+                .iter()
+                .map(|(algorithm, count)| (*algorithm, *count)),
+        );
+    }
+}
+
+/// Information about a homeserver that could not be reached while querying or claiming keys.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyFailure {
+    /// A failure in the usual shape of a Matrix error response.
+    Known {
+        /// The HTTP status code returned by the unreachable server, if any.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        status: Option<UInt>,
+
+        /// The Matrix error code of the failure, e.g. `M_UNKNOWN`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        errcode: Option<String>,
+
+        /// A human-readable description of the failure.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+
+        /// Any other fields the homeserver included in the failure.
+        #[serde(flatten)]
+        extra: HashMap<String, Value>,
+    },
+
+    /// A failure in any other shape, kept as is so the rest of the response can still be used.
+    Other(Value),
+}
+
+/// Whether the client has to upload the identity keys of the device it logged in with.
+///
+/// `login_device_id` is the `device_id` of a `login` response, `known_devices` are the devices
+/// the client already uploaded keys for. Logging in without reusing one of those devices creates
+/// a new device, which has no keys on the server yet.
+pub fn should_upload_device_keys(login_device_id: &str, known_devices: &[DeviceId]) -> bool {
+    !known_devices
+        .iter()
+        .any(|device_id| device_id == login_device_id)
+}
+
+/// Creates a request claiming a one-time key of the given algorithm for every device in the
+/// result of a `get_keys` request.
+pub fn claim_for_devices(
+    device_keys: &get_keys::Response,
+    algorithm: KeyAlgorithm,
+) -> claim_keys::Request {
+    device_keys
+        .device_keys
+        .iter()
+        .flat_map(|(user_id, devices)| {
+            devices
+                .keys()
+                .map(move |device_id| (user_id.clone(), device_id.clone()))
+        })
+        .fold(
+            claim_keys::Request::builder(),
+            |builder, (user_id, device_id)| builder.claim(user_id, device_id, algorithm),
+        )
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, convert::TryFrom};
+
+    use js_int::UInt;
+    use serde_json::{
+        from_slice as from_json_slice, from_value as from_json_value, json,
+        to_value as to_json_value, to_vec as to_json_vec, Value as JsonValue,
+    };
+
+    use super::{
+        claim_for_devices, get_keys, should_upload_device_keys, AlgorithmAndDeviceId, KeyAlgorithm,
+        KeyFailure, OneTimeKeyCounts, ParseKeyIdError,
+    };
+
+    #[test]
+    fn parse_algorithm_and_device_id() {
+        let key_id: AlgorithmAndDeviceId = "ed25519:ABCDEF".parse().unwrap();
+        assert_eq!(
+            key_id,
+            AlgorithmAndDeviceId(KeyAlgorithm::Ed25519, "ABCDEF".to_string())
+        );
+        assert_eq!(key_id.to_string(), "ed25519:ABCDEF");
+    }
+
+    #[test]
+    fn parse_unknown_algorithm() {
+        assert_eq!(
+            "rsa:ABCDEF".parse::<AlgorithmAndDeviceId>(),
+            Err(ParseKeyIdError::UnknownAlgorithm("rsa".to_string()))
+        );
+        assert_eq!(
+            "ABCDEF".parse::<AlgorithmAndDeviceId>(),
+            Err(ParseKeyIdError::MissingSeparator)
+        );
+    }
+
+    #[test]
+    fn device_id_with_colons_round_trip() {
+        let key_id: AlgorithmAndDeviceId =
+            from_json_value(json!("curve25519:DEV:WITH:COLONS")).unwrap();
+        assert_eq!(
+            key_id,
+            AlgorithmAndDeviceId(KeyAlgorithm::Curve25519, "DEV:WITH:COLONS".to_string())
+        );
+        assert_eq!(
+            to_json_value(&key_id).unwrap(),
+            json!("curve25519:DEV:WITH:COLONS")
+        );
+    }
+
+    #[test]
+    fn one_time_key_counts() {
+        let counts: OneTimeKeyCounts =
+            from_json_value(json!({ "curve25519": 10, "signed_curve25519": 20 })).unwrap();
+
+        assert_eq!(counts.signed_curve25519(), UInt::from(20u32));
+        assert_eq!(counts.get(KeyAlgorithm::Curve25519), UInt::from(10u32));
+        assert_eq!(counts.get(KeyAlgorithm::Ed25519), UInt::from(0u32));
+    }
+
+    #[test]
+    fn deserialize_key_failures() {
+        let failures: HashMap<String, KeyFailure> = from_json_value(json!({
+            "a.example.org": { "status": 503, "errcode": "M_UNKNOWN", "error": "Not ready" },
+            "b.example.org": { "status": "oops" },
+            "c.example.org": "timeout",
+        }))
+        .unwrap();
+
+        match &failures["a.example.org"] {
+            KeyFailure::Known {
+                status,
+                errcode,
+                error,
+                extra,
+            } => {
+                assert_eq!(*status, Some(UInt::from(503u32)));
+                assert_eq!(*errcode, Some("M_UNKNOWN".to_string()));
+                assert_eq!(*error, Some("Not ready".to_string()));
+                assert!(extra.is_empty());
+            }
+            KeyFailure::Other(value) => panic!("unexpected unknown failure {}", value),
+        }
+        assert_eq!(
+            failures["b.example.org"],
+            KeyFailure::Other(json!({ "status": "oops" }))
+        );
+        assert_eq!(
+            failures["c.example.org"],
+            KeyFailure::Other(json!("timeout"))
+        );
+    }
+
+    #[test]
+    fn upload_keys_for_new_device() {
+        let known_devices = vec!["PHONE".to_string(), "LAPTOP".to_string()];
+
+        assert!(should_upload_device_keys("TABLET", &known_devices));
+        assert!(should_upload_device_keys("TABLET", &[]));
+    }
+
+    #[test]
+    fn keep_keys_of_known_device() {
+        let known_devices = vec!["PHONE".to_string(), "LAPTOP".to_string()];
+
+        assert!(!should_upload_device_keys("LAPTOP", &known_devices));
+    }
+
+    #[test]
+    fn merge_one_time_key_counts() {
+        let mut counts: OneTimeKeyCounts =
+            from_json_value(json!({ "curve25519": 10, "signed_curve25519": 20 })).unwrap();
+        let newer: OneTimeKeyCounts =
+            from_json_value(json!({ "signed_curve25519": 5, "ed25519": 1 })).unwrap();
+
+        counts.merge(&newer);
+        assert_eq!(
+            to_json_value(&counts).unwrap(),
+            json!({ "curve25519": 10, "signed_curve25519": 5, "ed25519": 1 })
+        );
+    }
+
+    #[test]
+    fn claim_for_two_devices() {
+        let device_keys = |device_id: &str| {
+            json!({
+                "user_id": "@alice:example.org",
+                "device_id": device_id,
+                "algorithms": ["m.olm.v1.curve25519-aes-sha2"],
+                "keys": { format!("curve25519:{}", device_id): "key" },
+                "signatures": {},
+            })
+        };
+        let body = json!({
+            "failures": {},
+            "device_keys": {
+                "@alice:example.org": {
+                    "PHONE": device_keys("PHONE"),
+                    "LAPTOP": device_keys("LAPTOP"),
+                },
+            },
+        });
+        let http_response = http::Response::builder()
+            .body(to_json_vec(&body).unwrap())
+            .unwrap();
+        let response = get_keys::Response::try_from(http_response).unwrap();
+
+        let request = claim_for_devices(&response, KeyAlgorithm::SignedCurve25519);
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_request.body()).unwrap(),
+            json!({
+                "one_time_keys": {
+                    "@alice:example.org": {
+                        "PHONE": "signed_curve25519",
+                        "LAPTOP": "signed_curve25519",
+                    },
+                },
+            })
+        );
+    }
+}
@@ -5,9 +5,8 @@ use std::collections::HashMap;
 use js_int::UInt;
 use ruma_api::ruma_api;
 use ruma_identifiers::{DeviceId, UserId};
-use serde_json::Value;
 
-use super::{AlgorithmAndDeviceId, KeyAlgorithm, OneTimeKey};
+use super::{upload_keys, AlgorithmAndDeviceId, KeyAlgorithm, KeyFailure, OneTimeKey};
 
 ruma_api! {
     metadata {
@@ -32,9 +31,119 @@ ruma_api! {
     response {
         /// If any remote homeservers could not be reached, they are recorded here.
         /// The names of the properties are the names of the unreachable servers.
-        pub failures: HashMap<String, Value>,
+        pub failures: HashMap<String, KeyFailure>,
 
         /// One-time keys for the queried devices.
         pub one_time_keys: HashMap<UserId, HashMap<DeviceId, HashMap<AlgorithmAndDeviceId, OneTimeKey>>>,
     }
 }
+
+impl Request {
+    /// Creates a builder for a `claim_keys` request.
+    pub fn builder() -> RequestBuilder {
+        RequestBuilder::default()
+    }
+
+    /// Pairs this request with an `upload_keys` request that doesn't upload any keys.
+    ///
+    /// The response to this request doesn't contain the number of one-time keys left on the
+    /// server for the current device, but the response to the `upload_keys` request does. Sending
+    /// it after this request yields the up-to-date `OneTimeKeyCounts`.
+    pub fn with_key_counts_request(self) -> (Self, upload_keys::Request) {
+        (self, upload_keys::Request::builder().build())
+    }
+}
+
+/// A builder for a `claim_keys` request.
+///
+/// Keys are only added device by device, so the request never contains a user without any
+/// devices to claim keys of.
+#[derive(Clone, Debug, Default)]
+pub struct RequestBuilder {
+    timeout: Option<UInt>,
+    one_time_keys: HashMap<UserId, HashMap<DeviceId, KeyAlgorithm>>,
+}
+
+impl RequestBuilder {
+    /// Sets the time (in milliseconds) to wait when downloading keys from remote servers.
+    pub fn timeout(mut self, timeout: UInt) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Claims a one-time key of the given algorithm for a device of a user.
+    ///
+    /// Only a single algorithm can be claimed per device, there is no way to tell the server
+    /// about a list of preferred algorithms. Claiming a key for the same device again replaces
+    /// the previously set algorithm.
+    pub fn claim(mut self, user_id: UserId, device_id: DeviceId, algorithm: KeyAlgorithm) -> Self {
+        self.one_time_keys
+            .entry(user_id)
+            .or_default()
+            .insert(device_id, algorithm);
+        self
+    }
+
+    /// Creates the request.
+    pub fn build(self) -> Request {
+        Request {
+            timeout: self.timeout,
+            one_time_keys: self.one_time_keys,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use js_int::UInt;
+    use ruma_identifiers::UserId;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+    use crate::r0::keys::KeyAlgorithm;
+
+    #[test]
+    fn key_counts_request_uploads_no_keys() {
+        let (_, upload_request) = Request::builder().build().with_key_counts_request();
+
+        assert!(upload_request.device_keys.is_none());
+        assert!(upload_request.one_time_keys.is_none());
+        assert!(upload_request.fallback_keys.is_none());
+
+        let http_request = http::Request::<Vec<u8>>::try_from(upload_request).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_request.body()).unwrap(),
+            json!({})
+        );
+    }
+
+    #[test]
+    fn builder_json() {
+        let alice = UserId::try_from("@alice:example.org").unwrap();
+        let request = Request::builder()
+            .timeout(UInt::from(10_000u32))
+            .claim(
+                alice.clone(),
+                "PHONE".to_string(),
+                KeyAlgorithm::SignedCurve25519,
+            )
+            .claim(alice, "LAPTOP".to_string(), KeyAlgorithm::SignedCurve25519)
+            .build();
+
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_request.body()).unwrap(),
+            json!({
+                "timeout": 10_000,
+                "one_time_keys": {
+                    "@alice:example.org": {
+                        "PHONE": "signed_curve25519",
+                        "LAPTOP": "signed_curve25519",
+                    },
+                },
+            })
+        );
+    }
+}
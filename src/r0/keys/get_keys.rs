@@ -5,9 +5,8 @@ use std::collections::HashMap;
 use js_int::UInt;
 use ruma_api::ruma_api;
 use ruma_identifiers::{DeviceId, UserId};
-use serde_json::Value;
 
-use super::DeviceKeys;
+use super::{DeviceKeys, KeyFailure};
 
 ruma_api! {
     metadata {
@@ -38,7 +37,7 @@ ruma_api! {
     response {
         /// If any remote homeservers could not be reached, they are recorded here.
         /// The names of the properties are the names of the unreachable servers.
-        pub failures: HashMap<String, Value>,
+        pub failures: HashMap<String, KeyFailure>,
 
         /// Information on the queried devices.
         pub device_keys: HashMap<UserId, HashMap<DeviceId, DeviceKeys>>,
@@ -2,10 +2,9 @@
 
 use std::collections::HashMap;
 
-use js_int::UInt;
 use ruma_api::ruma_api;
 
-use super::{AlgorithmAndDeviceId, DeviceKeys, KeyAlgorithm, OneTimeKey};
+use super::{AlgorithmAndDeviceId, DeviceKeys, OneTimeKey, OneTimeKeyCounts};
 
 ruma_api! {
     metadata {
@@ -25,11 +24,155 @@ ruma_api! {
         /// One-time public keys for "pre-key" messages.
         #[serde(skip_serializing_if = "Option::is_none")]
         pub one_time_keys: Option<HashMap<AlgorithmAndDeviceId, OneTimeKey>>,
+
+        /// Fallback public keys for "pre-key" messages, used when the server runs out of one-time
+        /// keys for the device.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub fallback_keys: Option<HashMap<AlgorithmAndDeviceId, OneTimeKey>>,
     }
 
     response {
         /// For each key algorithm, the number of unclaimed one-time keys of that
         /// type currently held on the server for this device.
-        pub one_time_key_counts: HashMap<KeyAlgorithm, UInt>
+        pub one_time_key_counts: OneTimeKeyCounts
+    }
+}
+
+impl Request {
+    /// Creates a builder for an `upload_keys` request.
+    pub fn builder() -> RequestBuilder {
+        RequestBuilder::default()
+    }
+}
+
+/// A builder for an `upload_keys` request.
+///
+/// Fields that were not set are omitted from the request.
+#[derive(Clone, Debug, Default)]
+pub struct RequestBuilder {
+    device_keys: Option<DeviceKeys>,
+    one_time_keys: HashMap<AlgorithmAndDeviceId, OneTimeKey>,
+    fallback_keys: HashMap<AlgorithmAndDeviceId, OneTimeKey>,
+}
+
+impl RequestBuilder {
+    /// Sets the identity keys for the device.
+    pub fn device_keys(mut self, device_keys: DeviceKeys) -> Self {
+        self.device_keys = Some(device_keys);
+        self
+    }
+
+    /// Adds a one-time key with the given key ID.
+    pub fn add_one_time_key(mut self, id: AlgorithmAndDeviceId, key: OneTimeKey) -> Self {
+        self.one_time_keys.insert(id, key);
+        self
+    }
+
+    /// Adds a fallback key with the given key ID.
+    ///
+    /// The key is sent as is. The signature of a `OneTimeKey::SignedKey` has to cover its
+    /// `fallback` flag, so the caller has to set the flag before signing the key.
+    pub fn add_fallback_key(mut self, id: AlgorithmAndDeviceId, key: OneTimeKey) -> Self {
+        self.fallback_keys.insert(id, key);
+        self
+    }
+
+    /// Creates the request.
+    pub fn build(self) -> Request {
+        Request {
+            device_keys: self.device_keys,
+            one_time_keys: non_empty(self.one_time_keys),
+            fallback_keys: non_empty(self.fallback_keys),
+        }
+    }
+}
+
+fn non_empty(
+    keys: HashMap<AlgorithmAndDeviceId, OneTimeKey>,
+) -> Option<HashMap<AlgorithmAndDeviceId, OneTimeKey>> {
+    if keys.is_empty() {
+        None
+    } else {
+        Some(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, convert::TryFrom};
+
+    use ruma_identifiers::UserId;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+    use crate::r0::keys::{AlgorithmAndDeviceId, KeyAlgorithm, OneTimeKey, SignedKey};
+
+    fn signed_key(key: &str, fallback: bool) -> OneTimeKey {
+        let mut signatures = HashMap::new();
+        signatures.insert(
+            AlgorithmAndDeviceId(KeyAlgorithm::Ed25519, "DEVICE".to_string()),
+            "signature".to_string(),
+        );
+
+        let mut user_signatures = HashMap::new();
+        user_signatures.insert(UserId::try_from("@alice:example.org").unwrap(), signatures);
+
+        OneTimeKey::SignedKey(SignedKey {
+            key: key.to_string(),
+            signatures: user_signatures,
+            fallback,
+        })
+    }
+
+    #[test]
+    fn builder_json() {
+        let request = Request::builder()
+            .add_one_time_key(
+                AlgorithmAndDeviceId(KeyAlgorithm::SignedCurve25519, "AAAAAQ".to_string()),
+                signed_key("one_time", false),
+            )
+            .add_fallback_key(
+                AlgorithmAndDeviceId(KeyAlgorithm::SignedCurve25519, "AAAAAg".to_string()),
+                signed_key("fallback", true),
+            )
+            .build();
+
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_request.body()).unwrap(),
+            json!({
+                "one_time_keys": {
+                    "signed_curve25519:AAAAAQ": {
+                        "key": "one_time",
+                        "signatures": { "@alice:example.org": { "ed25519:DEVICE": "signature" } },
+                    },
+                },
+                "fallback_keys": {
+                    "signed_curve25519:AAAAAg": {
+                        "key": "fallback",
+                        "fallback": true,
+                        "signatures": { "@alice:example.org": { "ed25519:DEVICE": "signature" } },
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn fallback_key_is_sent_unchanged() {
+        let request = Request::builder()
+            .add_fallback_key(
+                AlgorithmAndDeviceId(KeyAlgorithm::SignedCurve25519, "AAAAAg".to_string()),
+                signed_key("fallback", false),
+            )
+            .build();
+
+        let fallback_keys = request.fallback_keys.unwrap();
+        match &fallback_keys
+            [&AlgorithmAndDeviceId(KeyAlgorithm::SignedCurve25519, "AAAAAg".to_string())]
+        {
+            OneTimeKey::SignedKey(signed_key) => assert!(!signed_key.fallback),
+            OneTimeKey::Key(_) => panic!("expected a signed key"),
+        }
     }
 }
@@ -12,9 +12,9 @@ pub mod kick_user;
 pub mod leave_room;
 pub mod unban_user;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, iter::FromIterator};
 
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
 
 // TODO: spec requires a nesting ThirdPartySigned { signed: Signed { mxid: ..., ... } }
 //       for join_room_by_id_or_alias but not for join_room_by_id, inconsistency?
@@ -32,3 +32,94 @@ pub struct ThirdPartySigned {
     /// The state key of the m.third_party_invite event.
     pub token: String,
 }
+
+/// A list of servers, sent as repeated `server_name` query parameters.
+///
+/// Query fields with a `Vec` type can't be serialized into a query string. This type can be used
+/// as the `query_map` field of a request instead, or be appended to the query string of any
+/// request with `WithExtraQuery`. Both turn it into `server_name=a&server_name=b`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServerNames(pub Vec<String>);
+
+impl ServerNames {
+    /// The key of the query parameters.
+    pub const KEY: &'static str = "server_name";
+}
+
+impl FromIterator<String> for ServerNames {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        ServerNames(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for ServerNames {
+    type Item = (String, String);
+    type IntoIter = std::iter::Map<std::vec::IntoIter<String>, fn(String) -> (String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        fn pair(server_name: String) -> (String, String) {
+            (ServerNames::KEY.to_string(), server_name)
+        }
+
+        self.0
+            .into_iter()
+            .map(pair as fn(String) -> (String, String))
+    }
+}
+
+impl Serialize for ServerNames {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for server_name in &self.0 {
+            seq.serialize_element(&(Self::KEY, server_name))?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ServerNames {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(String, String)>::deserialize(deserializer)?;
+
+        Ok(pairs
+            .into_iter()
+            .filter(|(key, _)| key == Self::KEY)
+            .map(|(_, server_name)| server_name)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ServerNames;
+
+    #[test]
+    fn server_names_query_round_trip() {
+        let server_names = ServerNames(vec![
+            "a.example.org".to_string(),
+            "b.example.org".to_string(),
+        ]);
+
+        let query = serde_urlencoded::to_string(&server_names).unwrap();
+        assert_eq!(query, "server_name=a.example.org&server_name=b.example.org");
+        assert_eq!(
+            serde_urlencoded::from_str::<ServerNames>(&query).unwrap(),
+            server_names
+        );
+    }
+
+    #[test]
+    fn ignore_other_query_parameters() {
+        assert_eq!(
+            serde_urlencoded::from_str::<ServerNames>("server_name=a&limit=10&server_name=b")
+                .unwrap(),
+            ServerNames(vec!["a".to_string(), "b".to_string()])
+        );
+    }
+}
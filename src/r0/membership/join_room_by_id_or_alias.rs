@@ -3,7 +3,8 @@
 use ruma_api::ruma_api;
 use ruma_identifiers::{RoomId, RoomIdOrAliasId};
 
-use super::ThirdPartySigned;
+use super::{ServerNames, ThirdPartySigned};
+use crate::extra_query::WithExtraQuery;
 
 ruma_api! {
     metadata {
@@ -30,3 +31,78 @@ ruma_api! {
         pub room_id: RoomId,
     }
 }
+
+impl Request {
+    /// The server name of the room alias, if the room is joined by an alias.
+    ///
+    /// The alias's homeserver is a good server to attempt the join through. The server name is
+    /// taken from the alias as it is sent in the request path. `RoomAliasId` doesn't keep track of
+    /// an explicit `:443` port, so that is left out of both.
+    pub fn server_names_hint(&self) -> Option<String> {
+        match &self.room_id_or_alias {
+            RoomIdOrAliasId::RoomAliasId(alias) => {
+                let alias = alias.to_string();
+                alias.find(':').map(|colon| alias[colon + 1..].to_string())
+            }
+            RoomIdOrAliasId::RoomId(_) => None,
+        }
+    }
+
+    /// Converts this request into an `http::Request` that attempts the join through the given
+    /// servers.
+    ///
+    /// One of the servers must be participating in the room.
+    pub fn into_http_request_with_server_names(
+        self,
+        server_names: ServerNames,
+    ) -> Result<http::Request<Vec<u8>>, ruma_api::Error> {
+        self.into_http_request_with_query(server_names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_identifiers::RoomIdOrAliasId;
+
+    use super::Request;
+    use crate::r0::membership::ServerNames;
+
+    fn request(room_id_or_alias: &str) -> Request {
+        Request {
+            room_id_or_alias: RoomIdOrAliasId::try_from(room_id_or_alias).unwrap(),
+            third_party_signed: None,
+        }
+    }
+
+    #[test]
+    fn server_names_hint_for_alias() {
+        assert_eq!(
+            request("#room:example.org").server_names_hint(),
+            Some("example.org".to_string())
+        );
+        assert_eq!(
+            request("#room:example.org:8448").server_names_hint(),
+            Some("example.org:8448".to_string())
+        );
+    }
+
+    #[test]
+    fn no_server_names_hint_for_id() {
+        assert_eq!(request("!room:example.org").server_names_hint(), None);
+    }
+
+    #[test]
+    fn join_through_server_names() {
+        let server_names = ServerNames(vec!["a".to_string(), "b".to_string()]);
+
+        let http_request = request("#room:example.org")
+            .into_http_request_with_server_names(server_names)
+            .unwrap();
+        assert_eq!(
+            http_request.uri().query(),
+            Some("server_name=a&server_name=b")
+        );
+    }
+}
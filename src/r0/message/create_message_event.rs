@@ -1,7 +1,13 @@
 //! [PUT /_matrix/client/r0/rooms/{roomId}/send/{eventType}/{txnId}](https://matrix.org/docs/spec/client_server/r0.4.0.html#put-matrix-client-r0-rooms-roomid-send-eventtype-txnid)
 
 use ruma_api::ruma_api;
-use ruma_events::{room::message::MessageEventContent, EventResult, EventType};
+use ruma_events::{
+    room::message::{
+        EmoteMessageEventContent, MessageEventContent, NoticeMessageEventContent,
+        TextMessageEventContent,
+    },
+    EventResult, EventType,
+};
 use ruma_identifiers::{EventId, RoomId};
 
 ruma_api! {
@@ -39,3 +45,84 @@ ruma_api! {
         pub event_id: EventId,
     }
 }
+
+impl Request {
+    /// Creates a request sending an `m.room.message` event with the given content.
+    pub fn room_message(
+        room_id: RoomId,
+        txn_id: impl Into<String>,
+        data: MessageEventContent,
+    ) -> Self {
+        Self {
+            room_id,
+            event_type: EventType::RoomMessage,
+            txn_id: txn_id.into(),
+            data,
+        }
+    }
+
+    /// Creates a request sending a plain text message.
+    pub fn text(room_id: RoomId, txn_id: impl Into<String>, body: impl Into<String>) -> Self {
+        Self::room_message(
+            room_id,
+            txn_id,
+            MessageEventContent::Text(TextMessageEventContent {
+                body: body.into(),
+                format: None,
+                formatted_body: None,
+                relates_to: None,
+            }),
+        )
+    }
+
+    /// Creates a request sending a plain text notice, a message sent by an automated client.
+    pub fn notice(room_id: RoomId, txn_id: impl Into<String>, body: impl Into<String>) -> Self {
+        Self::room_message(
+            room_id,
+            txn_id,
+            MessageEventContent::Notice(NoticeMessageEventContent {
+                body: body.into(),
+                relates_to: None,
+            }),
+        )
+    }
+
+    /// Creates a request sending a plain text emote, an action performed by the user.
+    pub fn emote(room_id: RoomId, txn_id: impl Into<String>, body: impl Into<String>) -> Self {
+        Self::room_message(
+            room_id,
+            txn_id,
+            MessageEventContent::Emote(EmoteMessageEventContent {
+                body: body.into(),
+                format: None,
+                formatted_body: None,
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_identifiers::RoomId;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn text_message() {
+        let room_id = RoomId::try_from("!room:example.org").unwrap();
+        let request = Request::text(room_id, "txn1", "Hello");
+
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+        assert_eq!(
+            http_request.uri().path(),
+            "/_matrix/client/r0/rooms/!room:example.org/send/m.room.message/txn1"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_request.body()).unwrap(),
+            json!({ "msgtype": "m.text", "body": "Hello" })
+        );
+    }
+}
@@ -5,6 +5,7 @@ use ruma_api::ruma_api;
 use ruma_events::{collections::all::RoomEvent, EventResult};
 use ruma_identifiers::RoomId;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::r0::filter::RoomEventFilter;
 
@@ -63,6 +64,26 @@ ruma_api! {
     }
 }
 
+/// Whether the given event of the `chunk` of an `IncomingResponse` has been redacted.
+///
+/// The content of a redacted event is stripped, so it will often fail to deserialize into its
+/// event type. Such events are kept in `chunk` as errors, and are checked using their JSON
+/// representation.
+pub fn is_redacted(event: &EventResult<RoomEvent>) -> bool {
+    match event {
+        EventResult::Ok(event) => serde_json::to_value(event)
+            .map(|json| has_redacted_because(&json))
+            .unwrap_or(false),
+        EventResult::Err(invalid_event) => has_redacted_because(invalid_event.json()),
+    }
+}
+
+fn has_redacted_because(json: &Value) -> bool {
+    json.get("unsigned")
+        .and_then(|unsigned| unsigned.get("redacted_because"))
+        .is_some()
+}
+
 /// The direction to return events from.
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum Direction {
@@ -73,3 +94,56 @@ pub enum Direction {
     #[serde(rename = "f")]
     Forward,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{is_redacted, IncomingResponse};
+
+    #[test]
+    fn redacted_event_in_chunk() {
+        let body = json!({
+            "start": "t1",
+            "end": "t2",
+            "chunk": [
+                {
+                    "type": "m.room.message",
+                    "content": { "msgtype": "m.text", "body": "Hello" },
+                    "event_id": "$message:example.org",
+                    "origin_server_ts": 1,
+                    "room_id": "!room:example.org",
+                    "sender": "@alice:example.org",
+                },
+                {
+                    "type": "m.room.message",
+                    "content": {},
+                    "event_id": "$redacted:example.org",
+                    "origin_server_ts": 2,
+                    "room_id": "!room:example.org",
+                    "sender": "@alice:example.org",
+                    "unsigned": {
+                        "redacted_because": {
+                            "type": "m.room.redaction",
+                            "content": {},
+                            "event_id": "$redaction:example.org",
+                            "origin_server_ts": 3,
+                            "redacts": "$redacted:example.org",
+                            "room_id": "!room:example.org",
+                            "sender": "@alice:example.org",
+                        },
+                    },
+                },
+            ],
+        });
+        let http_response = http::Response::builder()
+            .body(to_json_vec(&body).unwrap())
+            .unwrap();
+
+        let response = IncomingResponse::try_from(http_response).unwrap();
+        let redacted: Vec<_> = response.chunk.iter().map(is_redacted).collect();
+        assert_eq!(redacted, [false, true]);
+    }
+}
@@ -5,3 +5,114 @@ pub mod get_display_name;
 pub mod get_profile;
 pub mod set_avatar_url;
 pub mod set_display_name;
+
+use ruma_identifiers::UserId;
+
+/// Creates a `get_profile` request for each of the given users.
+///
+/// The specification has no endpoint to fetch the profiles of several users at once, so one
+/// request has to be sent per user.
+pub fn profiles_for(user_ids: &[UserId]) -> Vec<get_profile::Request> {
+    user_ids
+        .iter()
+        .map(|user_id| get_profile::Request {
+            user_id: user_id.clone(),
+        })
+        .collect()
+}
+
+/// Pairs the responses to the requests created by `profiles_for` with the users they belong to.
+///
+/// The responses have to be in the same order as the user IDs that were passed to
+/// `profiles_for`. If there are fewer responses than user IDs, the remaining user IDs are left
+/// out.
+pub fn collect<I>(user_ids: &[UserId], responses: I) -> Vec<(UserId, get_profile::Response)>
+where
+    I: IntoIterator<Item = get_profile::Response>,
+{
+    user_ids.iter().cloned().zip(responses).collect()
+}
+
+/// The maximum number of characters `normalize_display_name` keeps.
+pub const MAX_DISPLAY_NAME_LENGTH: usize = 256;
+
+/// Normalizes a display name for display in other clients.
+///
+/// Removes control characters, trims leading and trailing whitespace, collapses any other
+/// whitespace into single spaces and cuts the name off after `MAX_DISPLAY_NAME_LENGTH`
+/// characters.
+pub fn normalize_display_name(display_name: &str) -> String {
+    let normalized: String = display_name
+        .split_whitespace()
+        .map(|word| word.chars().filter(|c| !c.is_control()).collect::<String>())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(MAX_DISPLAY_NAME_LENGTH)
+        .collect();
+
+    normalized.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_identifiers::UserId;
+
+    use super::{
+        collect, get_profile, normalize_display_name, profiles_for, set_display_name,
+        MAX_DISPLAY_NAME_LENGTH,
+    };
+
+    #[test]
+    fn one_request_per_user() {
+        let user_ids = vec![
+            UserId::try_from("@alice:example.org").unwrap(),
+            UserId::try_from("@bob:example.org").unwrap(),
+            UserId::try_from("@carol:example.org").unwrap(),
+        ];
+
+        let requests = profiles_for(&user_ids);
+        assert_eq!(requests.len(), user_ids.len());
+        for (request, user_id) in requests.iter().zip(&user_ids) {
+            assert_eq!(request.user_id, *user_id);
+        }
+
+        let responses = vec![get_profile::Response {
+            avatar_url: None,
+            displayname: Some("Alice".to_string()),
+        }];
+        let profiles = collect(&user_ids, responses);
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].0, user_ids[0]);
+    }
+
+    #[test]
+    fn normalize_whitespace() {
+        assert_eq!(normalize_display_name("  Alice \t Smith\n"), "Alice Smith");
+        assert_eq!(normalize_display_name("Al\u{0}ice"), "Alice");
+        assert_eq!(normalize_display_name(" \u{7} "), "");
+    }
+
+    #[test]
+    fn normalize_long_name() {
+        let display_name = "a".repeat(MAX_DISPLAY_NAME_LENGTH + 10);
+        assert_eq!(
+            normalize_display_name(&display_name).chars().count(),
+            MAX_DISPLAY_NAME_LENGTH
+        );
+    }
+
+    #[test]
+    fn normalized_request() {
+        let user_id = UserId::try_from("@alice:example.org").unwrap();
+
+        let request = set_display_name::Request::normalized(user_id.clone(), " Alice ");
+        assert_eq!(request.displayname, Some("Alice".to_string()));
+
+        let request = set_display_name::Request::normalized(user_id, "   ");
+        assert_eq!(request.displayname, None);
+    }
+}
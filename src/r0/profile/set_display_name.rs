@@ -24,3 +24,23 @@ ruma_api! {
 
     response {}
 }
+
+impl Request {
+    /// Creates a request setting the display name of the user to the normalized form of
+    /// `displayname`, see `normalize_display_name`.
+    ///
+    /// Removes the display name if nothing is left after normalizing it. Set the fields directly
+    /// to send the display name as is.
+    pub fn normalized(user_id: UserId, displayname: &str) -> Self {
+        let displayname = super::normalize_display_name(displayname);
+
+        Self {
+            displayname: if displayname.is_empty() {
+                None
+            } else {
+                Some(displayname)
+            },
+            user_id,
+        }
+    }
+}
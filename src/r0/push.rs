@@ -1 +1,812 @@
 //! Endpoints for push notifications.
+
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    str::FromStr,
+};
+
+use js_int::{Int, UInt};
+use ruma_events::room::power_levels::PowerLevelsEventContent;
+use serde::{
+    de::{Error as _, IgnoredAny, MapAccess, Unexpected, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::Value as JsonValue;
+
+pub mod delete_pushrule;
+pub mod delete_pushrule_device_scope;
+pub mod get_notifications;
+pub mod get_pushrule;
+pub mod get_pushrule_device_scope;
+pub mod get_pushrules_device_scope;
+pub mod set_pusher;
+pub mod set_pushrule;
+pub mod set_pushrule_actions;
+pub mod set_pushrule_actions_device_scope;
+pub mod set_pushrule_device_scope;
+pub mod set_pushrule_enabled;
+pub mod set_pushrule_enabled_device_scope;
+
+/// The kind of a pusher.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PusherKind {
+    /// A pusher that sends HTTP pokes.
+    Http,
+    /// A pusher that emails the user with unread notifications.
+    Email,
+}
+
+/// Information for the pusher implementation itself.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PusherData {
+    /// The URL to use to send notifications to.
+    ///
+    /// Required if the pusher's kind is http.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The format to use when sending notifications to the push gateway, e.g. `event_id_only`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+/// The kinds of push rules that are available.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleKind {
+    /// User-configured rules that override all other kinds.
+    Override,
+    /// Lowest priority user-defined rules.
+    Underride,
+    /// Sender-specific rules.
+    Sender,
+    /// Room-specific rules.
+    Room,
+    /// Content-specific rules.
+    Content,
+}
+
+impl Display for RuleKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        let kind = match self {
+            RuleKind::Override => "override",
+            RuleKind::Underride => "underride",
+            RuleKind::Sender => "sender",
+            RuleKind::Room => "room",
+            RuleKind::Content => "content",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+/// A push rule.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PushRule {
+    /// The actions to perform when this rule is matched.
+    pub actions: Vec<Action>,
+    /// Whether this is a default rule, or has been set explicitly.
+    pub default: bool,
+    /// Whether the push rule is enabled or not.
+    pub enabled: bool,
+    /// The ID of this rule.
+    pub rule_id: String,
+    /// The conditions that must hold true for an event in order for a rule to be applied to an
+    /// event.
+    ///
+    /// A rule with no conditions always matches. Only applicable to underride and override rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<PushCondition>>,
+    /// The glob-style pattern to match against.
+    ///
+    /// Only applicable to content rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+impl PushRule {
+    /// The ID of the default rule notifying users about messages mentioning `@room`.
+    pub const ROOM_NOTIF: &'static str = ".m.rule.roomnotif";
+
+    /// Whether this is the rule notifying users about messages mentioning `@room`.
+    ///
+    /// This is either the default `.m.rule.roomnotif` rule, or a rule with the same conditions:
+    /// an `event_match` on the body of the message for `@room`, and a
+    /// `sender_notification_permission` check for the `room` key.
+    pub fn is_room_mention_rule(&self) -> bool {
+        if self.rule_id == Self::ROOM_NOTIF {
+            return true;
+        }
+
+        let conditions = match &self.conditions {
+            Some(conditions) => conditions,
+            None => return false,
+        };
+
+        let matches_body = conditions.iter().any(|condition| match condition {
+            PushCondition::EventMatch { key, pattern } => {
+                key == PushCondition::CONTENT_BODY && pattern == "@room"
+            }
+            _ => false,
+        });
+        let checks_permission = conditions.iter().any(|condition| match condition {
+            PushCondition::SenderNotificationPermission { key } => key == "room",
+            _ => false,
+        });
+
+        matches_body && checks_permission
+    }
+}
+
+/// A builder for user-defined push rules.
+///
+/// The rule notifies without any tweaks by default.
+#[derive(Clone, Debug)]
+pub struct PushRuleBuilder {
+    kind: RuleKind,
+    rule_id: String,
+    conditions: Vec<PushCondition>,
+    pattern: Option<String>,
+    notify: bool,
+    highlight: bool,
+    sound: Option<String>,
+}
+
+impl PushRuleBuilder {
+    /// Creates a builder for a rule of the given kind and ID.
+    pub fn new(kind: RuleKind, rule_id: impl Into<String>) -> Self {
+        Self {
+            kind,
+            rule_id: rule_id.into(),
+            conditions: Vec::new(),
+            pattern: None,
+            notify: true,
+            highlight: false,
+            sound: None,
+        }
+    }
+
+    /// Adds a condition to the rule.
+    ///
+    /// Only used by override and underride rules.
+    pub fn condition(mut self, condition: PushCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Sets the glob-style pattern of the rule.
+    ///
+    /// Only used by content rules.
+    pub fn pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Sets whether matching events generate a notification.
+    pub fn notify(mut self, notify: bool) -> Self {
+        self.notify = notify;
+        self
+    }
+
+    /// Sets whether notifications of matching events are highlighted.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Sets the sound to play for notifications of matching events, e.g. `default`.
+    pub fn sound(mut self, sound: impl Into<String>) -> Self {
+        self.sound = Some(sound.into());
+        self
+    }
+
+    /// Assembles the push rule.
+    ///
+    /// Fails if the rule ID is empty, a content rule has no pattern, or an override or underride
+    /// rule has no conditions. Tweaks are only added if the rule notifies.
+    pub fn build(self) -> Result<PushRule, PushRuleBuildError> {
+        if self.rule_id.is_empty() {
+            return Err(PushRuleBuildError::EmptyRuleId);
+        }
+
+        let (conditions, pattern) = match self.kind {
+            RuleKind::Content => match self.pattern {
+                Some(pattern) => (None, Some(pattern)),
+                None => return Err(PushRuleBuildError::MissingPattern),
+            },
+            RuleKind::Override | RuleKind::Underride => {
+                if self.conditions.is_empty() {
+                    return Err(PushRuleBuildError::MissingConditions);
+                }
+                (Some(self.conditions), None)
+            }
+            RuleKind::Sender | RuleKind::Room => (None, None),
+        };
+
+        let mut actions = Vec::new();
+        if self.notify {
+            actions.push(Action::Notify);
+            if let Some(sound) = self.sound {
+                actions.push(Action::SetTweak {
+                    kind: TweakKind::Sound,
+                    value: Some(JsonValue::String(sound)),
+                });
+            }
+            if self.highlight {
+                actions.push(Action::SetTweak {
+                    kind: TweakKind::Highlight,
+                    value: None,
+                });
+            }
+        } else {
+            actions.push(Action::DontNotify);
+        }
+
+        Ok(PushRule {
+            actions,
+            default: false,
+            enabled: true,
+            rule_id: self.rule_id,
+            conditions,
+            pattern,
+        })
+    }
+}
+
+/// An error when building a push rule with a `PushRuleBuilder`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PushRuleBuildError {
+    /// The rule ID is empty.
+    EmptyRuleId,
+    /// A content rule has no pattern.
+    MissingPattern,
+    /// An override or underride rule has no conditions.
+    MissingConditions,
+}
+
+impl Display for PushRuleBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        let message = match self {
+            PushRuleBuildError::EmptyRuleId => "the rule ID is empty",
+            PushRuleBuildError::MissingPattern => "content rules need a pattern",
+            PushRuleBuildError::MissingConditions => {
+                "override and underride rules need at least one condition"
+            }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl Error for PushRuleBuildError {}
+
+/// This represents the different actions that should be taken when a rule is matched, and
+/// controls how notifications are delivered to the client.
+///
+/// See https://matrix.org/docs/spec/client_server/r0.6.0#actions for details.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    /// Causes each matching event to generate a notification.
+    Notify,
+    /// Prevents each matching event from generating a notification.
+    DontNotify,
+    /// Behaves like notify but homeservers may choose to coalesce multiple events into a single
+    /// notification.
+    Coalesce,
+    /// Sets an entry in the 'tweaks' dictionary sent to the push gateway.
+    SetTweak {
+        /// The kind of this tweak.
+        kind: TweakKind,
+        /// The value of the tweak, if any.
+        value: Option<JsonValue>,
+    },
+}
+
+impl Action {
+    /// Whether this action sets the `highlight` tweak to `true`.
+    ///
+    /// A `highlight` tweak without a value counts as `true`.
+    pub fn is_highlight(&self) -> bool {
+        match self {
+            Action::SetTweak {
+                kind: TweakKind::Highlight,
+                value,
+            } => match value {
+                None => true,
+                Some(value) => value.as_bool() == Some(true),
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Serialize for Action {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Action::Notify => serializer.serialize_str("notify"),
+            Action::DontNotify => serializer.serialize_str("dont_notify"),
+            Action::Coalesce => serializer.serialize_str("coalesce"),
+            Action::SetTweak { kind, value } => {
+                let kind_name = match kind {
+                    TweakKind::Sound => "sound",
+                    TweakKind::Highlight => "highlight",
+                    TweakKind::Custom(name) => name,
+                };
+                let num_fields = if value.is_some() { 2 } else { 1 };
+
+                let mut s = serializer.serialize_struct("Action", num_fields)?;
+                s.serialize_field("set_tweak", kind_name)?;
+                if let Some(value) = value {
+                    s.serialize_field("value", value)?;
+                }
+                s.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Action {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ActionVisitor;
+
+        impl<'de> Visitor<'de> for ActionVisitor {
+            type Value = Action;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> Result<(), FmtError> {
+                write!(formatter, "an action string or a `set_tweak` object")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "notify" => Ok(Action::Notify),
+                    "dont_notify" => Ok(Action::DontNotify),
+                    "coalesce" => Ok(Action::Coalesce),
+                    other => Err(E::unknown_variant(
+                        other,
+                        &["notify", "dont_notify", "coalesce"],
+                    )),
+                }
+            }
+
+            fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+            where
+                M: MapAccess<'de>,
+            {
+                let mut kind = None;
+                let mut value = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "set_tweak" => {
+                            if kind.is_some() {
+                                return Err(M::Error::duplicate_field("set_tweak"));
+                            }
+
+                            let name: String = map.next_value()?;
+                            kind = Some(match name.as_str() {
+                                "sound" => TweakKind::Sound,
+                                "highlight" => TweakKind::Highlight,
+                                _ => TweakKind::Custom(name),
+                            });
+                        }
+                        "value" => {
+                            if value.is_some() {
+                                return Err(M::Error::duplicate_field("value"));
+                            }
+
+                            value = Some(map.next_value::<JsonValue>()?);
+                        }
+                        _ => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                match kind {
+                    Some(kind) => Ok(Action::SetTweak { kind, value }),
+                    None => Err(M::Error::missing_field("set_tweak")),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(ActionVisitor)
+    }
+}
+
+/// The different kinds of tweaks available.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TweakKind {
+    /// The "sound" tweak.
+    Sound,
+    /// The "highlight" tweak.
+    Highlight,
+    /// A name for a custom client-defined tweak.
+    Custom(String),
+}
+
+/// A condition for a push rule.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PushCondition {
+    /// This is a glob pattern match on a field of the event.
+    EventMatch {
+        /// The dot-separated field of the event to match.
+        key: String,
+        /// The glob-style pattern to match against.
+        ///
+        /// Patterns with no special glob characters should be treated as having asterisks
+        /// prepended and appended when testing the condition.
+        pattern: String,
+    },
+    /// This matches unencrypted messages where `content.body` contains the owner's display name
+    /// in that room.
+    ContainsDisplayName,
+    /// This matches the current number of members in the room.
+    RoomMemberCount {
+        /// The member count to compare against, e.g. `>=2`.
+        is: RoomMemberCountIs,
+    },
+    /// This takes into account the current power levels in the room, ensuring the sender of the
+    /// event has high enough power to trigger the notification.
+    SenderNotificationPermission {
+        /// The field in the power level event the user needs a minimum power level for.
+        ///
+        /// Fields must be specified under the `notifications` property in the power level event's
+        /// `content`.
+        key: String,
+    },
+}
+
+impl PushCondition {
+    /// The `key` of an `event_match` condition matching the body of a message.
+    pub const CONTENT_BODY: &'static str = "content.body";
+    /// The `key` of an `event_match` condition matching the message type of a message.
+    pub const CONTENT_MSGTYPE: &'static str = "content.msgtype";
+    /// The `key` of an `event_match` condition matching the type of an event.
+    pub const TYPE: &'static str = "type";
+    /// The `key` of an `event_match` condition matching the room an event was sent in.
+    pub const ROOM_ID: &'static str = "room_id";
+
+    /// Creates an `event_match` condition matching `pattern` against the field `key` of the
+    /// event.
+    pub fn event_match(key: impl Into<String>, pattern: impl Into<String>) -> Self {
+        PushCondition::EventMatch {
+            key: key.into(),
+            pattern: pattern.into(),
+        }
+    }
+}
+
+/// The power level a user needs to trigger the notification type `key`, as checked by a
+/// `sender_notification_permission` condition.
+///
+/// Defaults to 50 for notification types that aren't part of the power levels.
+pub fn notification_level(power_levels: &PowerLevelsEventContent, key: &str) -> Int {
+    match key {
+        "room" => power_levels.notifications.room,
+        _ => Int::from(50),
+    }
+}
+
+/// A comparison operator of a `room_member_count` push condition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComparisonOperator {
+    /// Equals (`==`). This is the default if no operator is given.
+    Eq,
+    /// Less than (`<`).
+    Lt,
+    /// Greater than (`>`).
+    Gt,
+    /// Greater or equal (`>=`).
+    Ge,
+    /// Less or equal (`<=`).
+    Le,
+}
+
+/// The member count a `room_member_count` push condition compares against.
+///
+/// In JSON, this is a decimal integer optionally prefixed by one of `==`, `<`, `>`, `>=` or `<=`.
+/// A prefix of `<` matches rooms where the member count is strictly less than the given number and
+/// so forth. If no prefix is present, the operator defaults to `==`.
+///
+/// Both `"2"` and `"==2"` parse as `ComparisonOperator::Eq`, which is always serialized without a
+/// prefix, i.e. as `"2"`. The two forms are equivalent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoomMemberCountIs {
+    /// How the member count of the room is compared to `count`.
+    pub operator: ComparisonOperator,
+    /// The member count to compare against.
+    pub count: UInt,
+}
+
+impl Display for RoomMemberCountIs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        let prefix = match self.operator {
+            ComparisonOperator::Eq => "",
+            ComparisonOperator::Lt => "<",
+            ComparisonOperator::Gt => ">",
+            ComparisonOperator::Ge => ">=",
+            ComparisonOperator::Le => "<=",
+        };
+        write!(f, "{}{}", prefix, self.count)
+    }
+}
+
+impl FromStr for RoomMemberCountIs {
+    type Err = ParseRoomMemberCountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let count_start = s.find(|c: char| c.is_ascii_digit()).unwrap_or(s.len());
+        let (prefix, count) = s.split_at(count_start);
+
+        let operator = match prefix {
+            "" | "==" => ComparisonOperator::Eq,
+            "<" => ComparisonOperator::Lt,
+            ">" => ComparisonOperator::Gt,
+            ">=" => ComparisonOperator::Ge,
+            "<=" => ComparisonOperator::Le,
+            _ => return Err(ParseRoomMemberCountError),
+        };
+
+        // `UInt` would also accept a leading `+`, which the specification doesn't allow.
+        if count.is_empty() || !count.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseRoomMemberCountError);
+        }
+
+        Ok(RoomMemberCountIs {
+            operator,
+            count: count.parse().map_err(|_| ParseRoomMemberCountError)?,
+        })
+    }
+}
+
+/// An error when parsing a `RoomMemberCountIs` from a string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseRoomMemberCountError;
+
+impl Display for ParseRoomMemberCountError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "expected a decimal integer optionally prefixed by one of ==, <, >, >= or <="
+        )
+    }
+}
+
+impl Error for ParseRoomMemberCountError {}
+
+impl Serialize for RoomMemberCountIs {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for RoomMemberCountIs {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+
+        value.parse().map_err(|_| {
+            D::Error::invalid_value(
+                Unexpected::Str(&value),
+                &"a decimal integer optionally prefixed by one of ==, <, >, >= or <=",
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use js_int::{Int, UInt};
+    use ruma_events::{room::power_levels::PowerLevelsEventContent, EventResult};
+
+    use super::{
+        notification_level, Action, ComparisonOperator, PushCondition, PushRule,
+        PushRuleBuildError, PushRuleBuilder, RoomMemberCountIs, RuleKind, TweakKind,
+    };
+
+    fn member_count_is(operator: ComparisonOperator, count: u32) -> RoomMemberCountIs {
+        RoomMemberCountIs {
+            operator,
+            count: UInt::from(count),
+        }
+    }
+
+    #[test]
+    fn parse_room_member_count_is() {
+        let cases = [
+            ("5", ComparisonOperator::Eq, 5),
+            ("==0", ComparisonOperator::Eq, 0),
+            ("<2", ComparisonOperator::Lt, 2),
+            (">2", ComparisonOperator::Gt, 2),
+            (">=2", ComparisonOperator::Ge, 2),
+            ("<=10", ComparisonOperator::Le, 10),
+        ];
+
+        for &(json, operator, count) in &cases {
+            let is: RoomMemberCountIs = from_json_value(json!(json)).unwrap();
+            assert_eq!(is, member_count_is(operator, count), "{}", json);
+        }
+    }
+
+    #[test]
+    fn serialize_room_member_count_is() {
+        let cases = [
+            (ComparisonOperator::Eq, "2"),
+            (ComparisonOperator::Lt, "<2"),
+            (ComparisonOperator::Gt, ">2"),
+            (ComparisonOperator::Ge, ">=2"),
+            (ComparisonOperator::Le, "<=2"),
+        ];
+
+        for &(operator, json) in &cases {
+            assert_eq!(
+                to_json_value(member_count_is(operator, 2)).unwrap(),
+                json!(json)
+            );
+        }
+    }
+
+    #[test]
+    fn reject_malformed_room_member_count_is() {
+        for json in &["=>3", "abc", "+5", "<+5", "", "==", "< 5"] {
+            assert!(
+                from_json_value::<RoomMemberCountIs>(json!(json)).is_err(),
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn set_tweak_without_value_round_trip() {
+        let json = json!({ "set_tweak": "highlight" });
+
+        let action: Action = from_json_value(json.clone()).unwrap();
+        assert_eq!(
+            action,
+            Action::SetTweak {
+                kind: TweakKind::Highlight,
+                value: None,
+            }
+        );
+        assert_eq!(to_json_value(&action).unwrap(), json);
+    }
+
+    #[test]
+    fn set_tweak_with_value_round_trip() {
+        let json = json!({ "set_tweak": "highlight", "value": false });
+
+        let action: Action = from_json_value(json.clone()).unwrap();
+        assert_eq!(
+            action,
+            Action::SetTweak {
+                kind: TweakKind::Highlight,
+                value: Some(json!(false)),
+            }
+        );
+        assert_eq!(to_json_value(&action).unwrap(), json);
+    }
+
+    #[test]
+    fn serialize_event_match() {
+        let condition = PushCondition::event_match(PushCondition::CONTENT_BODY, "cake");
+
+        assert_eq!(
+            to_json_value(&condition).unwrap(),
+            json!({
+                "kind": "event_match",
+                "key": "content.body",
+                "pattern": "cake",
+            })
+        );
+    }
+
+    #[test]
+    fn default_room_notif_rule_is_room_mention_rule() {
+        let mut rule: PushRule = from_json_value(json!({
+            "actions": ["notify", { "set_tweak": "highlight", "value": true }],
+            "conditions": [
+                { "kind": "event_match", "key": "content.body", "pattern": "@room" },
+                { "kind": "sender_notification_permission", "key": "room" },
+            ],
+            "default": true,
+            "enabled": true,
+            "rule_id": ".m.rule.roomnotif",
+        }))
+        .unwrap();
+        assert!(rule.is_room_mention_rule());
+
+        rule.rule_id = "room_mentions".to_string();
+        assert!(rule.is_room_mention_rule());
+
+        rule.conditions = Some(vec![PushCondition::event_match(
+            PushCondition::CONTENT_BODY,
+            "@room",
+        )]);
+        assert!(!rule.is_room_mention_rule());
+    }
+
+    #[test]
+    fn notification_levels() {
+        let power_levels = from_json_value::<EventResult<PowerLevelsEventContent>>(json!({
+            "events": {},
+            "notifications": { "room": 20 },
+            "users": {},
+        }))
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+        assert_eq!(notification_level(&power_levels, "room"), Int::from(20));
+        assert_eq!(notification_level(&power_levels, "unknown"), Int::from(50));
+    }
+
+    #[test]
+    fn default_notification_levels() {
+        let power_levels = from_json_value::<EventResult<PowerLevelsEventContent>>(json!({
+            "events": {},
+            "notifications": {},
+            "users": {},
+        }))
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+        assert_eq!(notification_level(&power_levels, "room"), Int::from(50));
+    }
+
+    #[test]
+    fn build_override_rule() {
+        let rule = PushRuleBuilder::new(RuleKind::Override, "cake")
+            .condition(PushCondition::event_match(
+                PushCondition::CONTENT_BODY,
+                "cake",
+            ))
+            .highlight(true)
+            .sound("default")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            to_json_value(&rule).unwrap(),
+            json!({
+                "actions": [
+                    "notify",
+                    { "set_tweak": "sound", "value": "default" },
+                    { "set_tweak": "highlight" },
+                ],
+                "conditions": [
+                    { "kind": "event_match", "key": "content.body", "pattern": "cake" },
+                ],
+                "default": false,
+                "enabled": true,
+                "rule_id": "cake",
+            })
+        );
+    }
+
+    #[test]
+    fn reject_content_rule_without_pattern() {
+        assert_eq!(
+            PushRuleBuilder::new(RuleKind::Content, "cake").build(),
+            Err(PushRuleBuildError::MissingPattern)
+        );
+    }
+}
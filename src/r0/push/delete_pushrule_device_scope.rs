@@ -0,0 +1,58 @@
+//! [DELETE /_matrix/client/r0/pushrules/device/{profileTag}/{kind}/{ruleId}](https://matrix.org/docs/spec/client_server/r0.6.0#delete-matrix-client-r0-pushrules-scope-kind-ruleid)
+
+use ruma_api::ruma_api;
+
+use super::RuleKind;
+
+ruma_api! {
+    metadata {
+        description: "This endpoint removes the push rule defined in the path.",
+        method: DELETE,
+        name: "delete_pushrule_device_scope",
+        path: "/_matrix/client/r0/pushrules/device/:profile_tag/:kind/:rule_id",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    request {
+        /// The profile tag of the device the rule belongs to.
+        #[ruma_api(path)]
+        pub profile_tag: String,
+        /// The kind of rule.
+        #[ruma_api(path)]
+        pub kind: RuleKind,
+        /// The identifier for the rule.
+        #[ruma_api(path)]
+        pub rule_id: String,
+    }
+
+    response {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::Request;
+    use crate::r0::push::RuleKind;
+
+    #[test]
+    fn request_url() {
+        let request = Request {
+            profile_tag: "mytag".to_string(),
+            kind: RuleKind::Content,
+            rule_id: "nickname".to_string(),
+        };
+
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+        assert_eq!(
+            http_request.uri().path(),
+            "/_matrix/client/r0/pushrules/device/mytag/content/nickname"
+        );
+
+        let request = Request::try_from(http_request).unwrap();
+        assert_eq!(request.profile_tag, "mytag");
+        assert_eq!(request.kind, RuleKind::Content);
+        assert_eq!(request.rule_id, "nickname");
+    }
+}
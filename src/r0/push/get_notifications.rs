@@ -0,0 +1,432 @@
+//! [GET /_matrix/client/r0/notifications](https://matrix.org/docs/spec/client_server/r0.6.0#get-matrix-client-r0-notifications)
+
+use std::fmt::{Debug, Error as FmtError, Formatter};
+
+use js_int::UInt;
+use ruma_api::{ruma_api, Outgoing};
+use ruma_events::{collections::all::Event, EventResult};
+use ruma_identifiers::RoomId;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use super::{Action, TweakKind};
+
+ruma_api! {
+    metadata {
+        description: "Paginate through the list of events that the user has been, or would have been notified about.",
+        method: GET,
+        name: "get_notifications",
+        path: "/_matrix/client/r0/notifications",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    request {
+        /// Pagination token given to retrieve the next set of events.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[ruma_api(query)]
+        pub from: Option<String>,
+        /// Limit on the number of events to return in this request.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[ruma_api(query)]
+        pub limit: Option<UInt>,
+        /// Allows basic filtering of events returned.
+        ///
+        /// Supply "highlight" to return only events where the notification had the 'highlight'
+        /// tweak set.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[ruma_api(query)]
+        pub only: Option<String>,
+    }
+
+    response {
+        /// The token to supply in the `from` param of the next `/notifications` request in order
+        /// to request more events.
+        ///
+        /// If this is absent, there are no more results.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub next_token: Option<String>,
+        /// The list of events that triggered notifications.
+        #[wrap_incoming(Notification)]
+        pub notifications: Vec<Notification>,
+    }
+}
+
+impl Request {
+    /// The value of `only` requesting only notifications with the `highlight` tweak set.
+    pub const HIGHLIGHT: &'static str = "highlight";
+
+    /// Whether this request asks for highlighted notifications only.
+    pub fn is_highlight_only(&self) -> bool {
+        match &self.only {
+            Some(only) => only == Self::HIGHLIGHT,
+            None => false,
+        }
+    }
+}
+
+/// A page of notifications, along with the filter it was requested with.
+///
+/// Pages requested with and without `only` set to `highlight` contain different notifications,
+/// which matters when caching both.
+pub struct NotificationsPage {
+    /// Whether the page contains highlighted notifications only.
+    pub highlight_only: bool,
+    /// The response containing the notifications.
+    pub response: IncomingResponse,
+}
+
+impl NotificationsPage {
+    /// Pairs a response with the filter of the request it was returned for.
+    pub fn new(request: &Request, response: IncomingResponse) -> Self {
+        Self {
+            highlight_only: request.is_highlight_only(),
+            response,
+        }
+    }
+}
+
+impl Debug for NotificationsPage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.debug_struct("NotificationsPage")
+            .field("highlight_only", &self.highlight_only)
+            .field("next_token", &self.response.next_token)
+            .field("notification_count", &self.response.notifications.len())
+            .finish()
+    }
+}
+
+impl Response {
+    /// The number of notifications in this page that set the `highlight` tweak.
+    pub fn highlight_count(&self) -> usize {
+        highlight_count(
+            self.notifications
+                .iter()
+                .map(|notification| &notification.actions),
+        )
+    }
+
+    /// The timestamp of the newest notification in this page.
+    ///
+    /// Notifications can only be paginated backwards in time, from the newest to the oldest. To
+    /// find out whether there are new notifications since a cached page, fetch the first page
+    /// again (without `from`) and compare its newest timestamp against the cached one.
+    pub fn newest_ts(&self) -> Option<UInt> {
+        newest_ts(
+            self.notifications
+                .iter()
+                .map(|notification| notification.ts),
+        )
+    }
+}
+
+impl IncomingResponse {
+    /// The number of notifications in this page that set the `highlight` tweak.
+    pub fn highlight_count(&self) -> usize {
+        highlight_count(
+            self.notifications
+                .iter()
+                .map(|notification| &notification.actions),
+        )
+    }
+
+    /// The timestamp of the newest notification in this page.
+    ///
+    /// Notifications can only be paginated backwards in time, from the newest to the oldest. To
+    /// find out whether there are new notifications since a cached page, fetch the first page
+    /// again (without `from`) and compare its newest timestamp against the cached one.
+    pub fn newest_ts(&self) -> Option<UInt> {
+        newest_ts(
+            self.notifications
+                .iter()
+                .map(|notification| notification.ts),
+        )
+    }
+}
+
+/// Represents a notification.
+#[derive(Clone, Debug, Serialize, Outgoing)]
+pub struct Notification {
+    /// The actions to perform when the conditions for this rule are met.
+    pub actions: Vec<Action>,
+    /// The event that triggered the notification.
+    #[wrap_incoming(with EventResult)]
+    pub event: Event,
+    /// The profile tag of the rule that matched this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_tag: Option<String>,
+    /// Indicates whether the user has sent a read receipt indicating that they have read this
+    /// message.
+    pub read: bool,
+    /// The ID of the room in which the event was posted.
+    pub room_id: RoomId,
+    /// The unix timestamp in milliseconds at which the event notification was sent.
+    pub ts: UInt,
+}
+
+impl Notification {
+    /// The value of the `sound` tweak denoting the default notification sound of the client.
+    pub const DEFAULT_SOUND: &'static str = "default";
+
+    /// Whether the event that triggered this notification is an `m.room.encrypted` event.
+    ///
+    /// Encrypted events have to be decrypted before they can be displayed.
+    // `matches!` requires Rust 1.42.
+    #[allow(clippy::match_like_matches_macro)]
+    pub fn is_encrypted(&self) -> bool {
+        match self.event {
+            Event::RoomEncrypted(_) => true,
+            _ => false,
+        }
+    }
+
+    /// The sound to play for this notification, as set by a `sound` tweak.
+    ///
+    /// The sound is either the name of a sound file, or `Notification::DEFAULT_SOUND` for the
+    /// default notification sound of the client.
+    pub fn sound(&self) -> Option<&str> {
+        sound(&self.actions)
+    }
+}
+
+impl IncomingNotification {
+    /// Whether the event that triggered this notification is an `m.room.encrypted` event.
+    ///
+    /// Encrypted events have to be decrypted before they can be displayed. For an invalid event,
+    /// the `type` field of its JSON representation is checked.
+    pub fn is_encrypted(&self) -> bool {
+        match &self.event {
+            EventResult::Ok(Event::RoomEncrypted(_)) => true,
+            EventResult::Ok(_) => false,
+            EventResult::Err(invalid_event) => {
+                invalid_event.json().get("type").and_then(JsonValue::as_str)
+                    == Some("m.room.encrypted")
+            }
+        }
+    }
+
+    /// The sound to play for this notification, as set by a `sound` tweak.
+    ///
+    /// The sound is either the name of a sound file, or `Notification::DEFAULT_SOUND` for the
+    /// default notification sound of the client.
+    pub fn sound(&self) -> Option<&str> {
+        sound(&self.actions)
+    }
+}
+
+fn highlight_count<'a>(actions: impl Iterator<Item = &'a Vec<Action>>) -> usize {
+    actions
+        .filter(|actions| actions.iter().any(Action::is_highlight))
+        .count()
+}
+
+fn newest_ts(timestamps: impl Iterator<Item = UInt>) -> Option<UInt> {
+    timestamps.max()
+}
+
+fn sound(actions: &[Action]) -> Option<&str> {
+    actions.iter().find_map(|action| match action {
+        Action::SetTweak {
+            kind: TweakKind::Sound,
+            value: Some(value),
+        } => value.as_str(),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use js_int::UInt;
+    use ruma_events::EventResult;
+    use serde_json::{from_value as from_json_value, json, Value as JsonValue};
+
+    use super::{IncomingNotification, IncomingResponse, Notification, NotificationsPage, Request};
+    use crate::r0::push::{Action, TweakKind};
+
+    fn message_notification(actions: JsonValue, ts: u64) -> JsonValue {
+        json!({
+            "actions": actions,
+            "event": {
+                "type": "m.room.message",
+                "content": { "msgtype": "m.text", "body": "Hello" },
+                "event_id": "$event:example.org",
+                "origin_server_ts": ts,
+                "room_id": "!room:example.org",
+                "sender": "@alice:example.org",
+            },
+            "read": false,
+            "room_id": "!room:example.org",
+            "ts": ts,
+        })
+    }
+
+    fn response(notifications: Vec<JsonValue>) -> IncomingResponse {
+        let body = json!({ "notifications": notifications });
+        let http_response = http::Response::builder()
+            .body(serde_json::to_vec(&body).unwrap())
+            .unwrap();
+
+        IncomingResponse::try_from(http_response).unwrap()
+    }
+
+    fn notification(event_type: &str, content: JsonValue) -> IncomingNotification {
+        from_json_value(json!({
+            "actions": ["notify"],
+            "event": {
+                "type": event_type,
+                "content": content,
+                "event_id": "$event:example.org",
+                "origin_server_ts": 1,
+                "room_id": "!room:example.org",
+                "sender": "@alice:example.org",
+            },
+            "read": false,
+            "room_id": "!room:example.org",
+            "ts": 1,
+        }))
+        .unwrap()
+    }
+
+    fn into_outgoing(notification: IncomingNotification) -> Notification {
+        let event = match notification.event {
+            EventResult::Ok(event) => event,
+            EventResult::Err(invalid_event) => panic!("invalid event: {}", invalid_event),
+        };
+
+        Notification {
+            actions: notification.actions,
+            event,
+            profile_tag: notification.profile_tag,
+            read: notification.read,
+            room_id: notification.room_id,
+            ts: notification.ts,
+        }
+    }
+
+    #[test]
+    fn encrypted_notification() {
+        let notification = notification(
+            "m.room.encrypted",
+            json!({
+                "algorithm": "m.megolm.v1.aes-sha2",
+                "ciphertext": "ciphertext",
+                "device_id": "DEVICE",
+                "sender_key": "sender_key",
+                "session_id": "session_id",
+            }),
+        );
+
+        assert!(notification.is_encrypted());
+        assert!(into_outgoing(notification).is_encrypted());
+    }
+
+    #[test]
+    fn cleartext_notification() {
+        let notification = notification(
+            "m.room.message",
+            json!({ "msgtype": "m.text", "body": "Hello" }),
+        );
+
+        assert!(!notification.is_encrypted());
+        assert!(!into_outgoing(notification).is_encrypted());
+    }
+
+    #[test]
+    fn highlight_count() {
+        let response = response(vec![
+            message_notification(json!(["notify", { "set_tweak": "highlight" }]), 3),
+            message_notification(json!(["notify"]), 2),
+            message_notification(
+                json!(["notify", { "set_tweak": "highlight", "value": false }]),
+                1,
+            ),
+            message_notification(
+                json!(["notify", { "set_tweak": "highlight", "value": true }]),
+                0,
+            ),
+        ]);
+
+        assert_eq!(response.highlight_count(), 2);
+    }
+
+    #[test]
+    fn typed_tweaks() {
+        let notification: IncomingNotification = from_json_value(message_notification(
+            json!([
+                "notify",
+                { "set_tweak": "sound", "value": "default" },
+                { "set_tweak": "highlight" },
+            ]),
+            1,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            notification.actions,
+            vec![
+                Action::Notify,
+                Action::SetTweak {
+                    kind: TweakKind::Sound,
+                    value: Some(json!("default")),
+                },
+                Action::SetTweak {
+                    kind: TweakKind::Highlight,
+                    value: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn newest_ts() {
+        let page = response(vec![
+            message_notification(json!(["notify"]), 20),
+            message_notification(json!(["notify"]), 30),
+            message_notification(json!(["notify"]), 10),
+        ]);
+        assert_eq!(page.newest_ts(), Some(UInt::from(30u32)));
+
+        assert_eq!(response(Vec::new()).newest_ts(), None);
+    }
+
+    #[test]
+    fn notification_sound() {
+        let sound = |actions: JsonValue| {
+            from_json_value::<IncomingNotification>(message_notification(actions, 1))
+                .unwrap()
+                .sound()
+                .map(ToOwned::to_owned)
+        };
+
+        assert_eq!(
+            sound(json!(["notify", { "set_tweak": "sound", "value": "ping.ogg" }])),
+            Some("ping.ogg".to_string())
+        );
+        assert_eq!(
+            sound(json!(["notify", { "set_tweak": "sound", "value": "default" }])),
+            Some(Notification::DEFAULT_SOUND.to_string())
+        );
+        assert_eq!(sound(json!(["notify", { "set_tweak": "highlight" }])), None);
+    }
+
+    #[test]
+    fn highlight_only_page() {
+        let request = Request {
+            from: None,
+            limit: None,
+            only: Some(Request::HIGHLIGHT.to_string()),
+        };
+        let page = NotificationsPage::new(&request, response(Vec::new()));
+        assert!(page.highlight_only);
+
+        let request = Request {
+            from: None,
+            limit: None,
+            only: None,
+        };
+        let page = NotificationsPage::new(&request, response(Vec::new()));
+        assert!(!page.highlight_only);
+    }
+}
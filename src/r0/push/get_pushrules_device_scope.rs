@@ -0,0 +1,73 @@
+//! [GET /_matrix/client/r0/pushrules/device/{profileTag}/](https://matrix.org/docs/spec/client_server/r0.6.0#push-rules-api)
+
+use std::collections::HashMap;
+
+use ruma_api::ruma_api;
+
+use super::{PushRule, RuleKind};
+
+ruma_api! {
+    metadata {
+        description: "Retrieve the push rules of the device with the given profile tag.",
+        method: GET,
+        name: "get_pushrules_device_scope",
+        path: "/_matrix/client/r0/pushrules/device/:profile_tag/",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    request {
+        /// The profile tag of the device to retrieve the push rules of.
+        #[ruma_api(path)]
+        pub profile_tag: String,
+    }
+
+    response {
+        /// The device-specific push rules, grouped by their kind.
+        #[ruma_api(body)]
+        pub rules: HashMap<RuleKind, Vec<PushRule>>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::Response;
+    use crate::r0::push::RuleKind;
+
+    #[test]
+    fn deserialize_rules_by_kind() {
+        let body = json!({
+            "content": [
+                {
+                    "actions": ["notify"],
+                    "default": false,
+                    "enabled": true,
+                    "pattern": "cake",
+                    "rule_id": "cake",
+                }
+            ],
+            "override": [
+                {
+                    "actions": ["dont_notify"],
+                    "conditions": [],
+                    "default": false,
+                    "enabled": true,
+                    "rule_id": "silence",
+                }
+            ],
+        });
+        let http_response = http::Response::builder()
+            .body(to_json_vec(&body).unwrap())
+            .unwrap();
+
+        let response = Response::try_from(http_response).unwrap();
+        assert_eq!(response.rules.len(), 2);
+        assert_eq!(response.rules[&RuleKind::Content][0].rule_id, "cake");
+        assert_eq!(response.rules[&RuleKind::Override][0].rule_id, "silence");
+        assert!(!response.rules.contains_key(&RuleKind::Room));
+    }
+}
@@ -0,0 +1,126 @@
+//! [POST /_matrix/client/r0/pushers/set](https://matrix.org/docs/spec/client_server/r0.6.0#post-matrix-client-r0-pushers-set)
+
+use ruma_api::ruma_api;
+
+use super::{PusherData, PusherKind};
+
+ruma_api! {
+    metadata {
+        description: "This endpoint allows the creation, modification and deletion of pushers for this user ID.",
+        method: POST,
+        name: "set_pusher",
+        path: "/_matrix/client/r0/pushers/set",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    request {
+        /// A unique identifier for this pusher.
+        pub pushkey: String,
+        /// The kind of the pusher.
+        ///
+        /// `None` deletes the pusher, the fields other than `pushkey` and `app_id` are not needed
+        /// in that case.
+        pub kind: Option<PusherKind>,
+        /// A reverse-DNS style identifier for the application.
+        pub app_id: String,
+        /// A string that will allow the user to identify what application owns this pusher.
+        ///
+        /// Required unless deleting the pusher.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub app_display_name: Option<String>,
+        /// A string that will allow the user to identify what device owns this pusher.
+        ///
+        /// Required unless deleting the pusher.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub device_display_name: Option<String>,
+        /// This string determines which set of device specific rules this pusher executes.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub profile_tag: Option<String>,
+        /// The preferred language for receiving notifications, e.g. 'en' or 'en-US'.
+        ///
+        /// Required unless deleting the pusher.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub lang: Option<String>,
+        /// Information for the pusher implementation itself.
+        ///
+        /// Required unless deleting the pusher.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub data: Option<PusherData>,
+        /// Whether to keep other pushers with the same pushkey for other users, instead of
+        /// replacing them.
+        ///
+        /// Defaults to `false`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub append: Option<bool>,
+    }
+
+    response {}
+}
+
+impl Request {
+    /// Creates a request deleting the pusher with the given pushkey and app ID.
+    ///
+    /// `append` is left unset, since it doesn't apply to deletions.
+    pub fn delete(pushkey: impl Into<String>, app_id: impl Into<String>) -> Self {
+        Self {
+            pushkey: pushkey.into(),
+            kind: None,
+            app_id: app_id.into(),
+            app_display_name: None,
+            device_display_name: None,
+            profile_tag: None,
+            lang: None,
+            data: None,
+            append: None,
+        }
+    }
+
+    /// Creates requests deleting each of the pushers with the given pushkey and app ID pairs.
+    pub fn delete_all<I, P, A>(pushers: I) -> Vec<Self>
+    where
+        I: IntoIterator<Item = (P, A)>,
+        P: Into<String>,
+        A: Into<String>,
+    {
+        pushers
+            .into_iter()
+            .map(|(pushkey, app_id)| Self::delete(pushkey, app_id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn delete_all_pushers() {
+        let requests = Request::delete_all(vec![
+            ("key1", "org.example.app"),
+            ("key2", "org.example.app"),
+            ("key3", "org.example.app.beta"),
+        ]);
+        assert_eq!(requests.len(), 3);
+
+        let bodies: Vec<JsonValue> = requests
+            .into_iter()
+            .map(|request| {
+                let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+                from_json_slice(http_request.body()).unwrap()
+            })
+            .collect();
+        assert_eq!(
+            bodies,
+            vec![
+                json!({ "pushkey": "key1", "kind": null, "app_id": "org.example.app" }),
+                json!({ "pushkey": "key2", "kind": null, "app_id": "org.example.app" }),
+                json!({ "pushkey": "key3", "kind": null, "app_id": "org.example.app.beta" }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! [PUT /_matrix/client/r0/pushrules/device/{profileTag}/{kind}/{ruleId}](https://matrix.org/docs/spec/client_server/r0.6.0#put-matrix-client-r0-pushrules-scope-kind-ruleid)
+
+use ruma_api::ruma_api;
+
+use super::{Action, PushCondition, RuleKind};
+
+ruma_api! {
+    metadata {
+        description: "This endpoint allows the creation and modification of user defined push rules.",
+        method: PUT,
+        name: "set_pushrule_device_scope",
+        path: "/_matrix/client/r0/pushrules/device/:profile_tag/:kind/:rule_id",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    request {
+        /// The profile tag of the device the rule belongs to.
+        #[ruma_api(path)]
+        pub profile_tag: String,
+        /// The kind of rule.
+        #[ruma_api(path)]
+        pub kind: RuleKind,
+        /// The identifier for the rule.
+        #[ruma_api(path)]
+        pub rule_id: String,
+        /// Use 'before' with a rule_id as its value to make the new rule the next-most important
+        /// rule with respect to the given user defined rule.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[ruma_api(query)]
+        pub before: Option<String>,
+        /// This makes the new rule the next-less important rule relative to the given user defined
+        /// rule.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[ruma_api(query)]
+        pub after: Option<String>,
+        /// The actions to perform when this rule is matched.
+        pub actions: Vec<Action>,
+        /// The conditions that must hold true for an event in order for a rule to be applied to an
+        /// event.
+        ///
+        /// Only applicable to underride and override rules, which require it, `None` otherwise.
+        /// `Some` with an empty Vec creates a rule that always matches.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub conditions: Option<Vec<PushCondition>>,
+        /// The glob-style pattern to match against.
+        ///
+        /// Only applicable to content rules.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub pattern: Option<String>,
+    }
+
+    response {}
+}
@@ -0,0 +1,101 @@
+//! [PUT /_matrix/client/r0/pushrules/global/{kind}/{ruleId}/enabled](https://matrix.org/docs/spec/client_server/r0.6.0#put-matrix-client-r0-pushrules-scope-kind-ruleid-enabled)
+
+use ruma_api::ruma_api;
+
+use super::RuleKind;
+
+ruma_api! {
+    metadata {
+        description: "This endpoint allows clients to enable or disable the specified push rule.",
+        method: PUT,
+        name: "set_pushrule_enabled",
+        path: "/_matrix/client/r0/pushrules/global/:kind/:rule_id/enabled",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    request {
+        /// The kind of rule.
+        #[ruma_api(path)]
+        pub kind: RuleKind,
+        /// The identifier for the rule.
+        #[ruma_api(path)]
+        pub rule_id: String,
+        /// Whether the push rule is enabled or not.
+        pub enabled: bool,
+    }
+
+    response {}
+}
+
+impl Request {
+    /// Creates a request enabling the global push rule with the given kind and ID.
+    ///
+    /// Returns `None` if `rule_id` is empty.
+    pub fn enable_global(kind: RuleKind, rule_id: impl Into<String>) -> Option<Self> {
+        let rule_id = rule_id.into();
+        if rule_id.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            kind,
+            rule_id,
+            enabled: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+    use crate::r0::push::RuleKind;
+
+    #[test]
+    fn disabled_rule_round_trip() {
+        let request = Request {
+            kind: RuleKind::Override,
+            rule_id: ".m.rule.suppress_notices".to_string(),
+            enabled: false,
+        };
+
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+        assert_eq!(
+            http_request.uri().path(),
+            "/_matrix/client/r0/pushrules/global/override/.m.rule.suppress_notices/enabled"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_request.body()).unwrap(),
+            json!({ "enabled": false })
+        );
+
+        let request = Request::try_from(http_request).unwrap();
+        assert_eq!(request.kind, RuleKind::Override);
+        assert_eq!(request.rule_id, ".m.rule.suppress_notices");
+        assert!(!request.enabled);
+    }
+
+    #[test]
+    fn enable_default_rule() {
+        let request = Request::enable_global(RuleKind::Underride, ".m.rule.call").unwrap();
+
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+        assert_eq!(
+            http_request.uri().path(),
+            "/_matrix/client/r0/pushrules/global/underride/.m.rule.call/enabled"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_request.body()).unwrap(),
+            json!({ "enabled": true })
+        );
+    }
+
+    #[test]
+    fn enable_rule_with_empty_id() {
+        assert!(Request::enable_global(RuleKind::Underride, "").is_none());
+    }
+}
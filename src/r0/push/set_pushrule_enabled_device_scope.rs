@@ -0,0 +1,68 @@
+//! [PUT /_matrix/client/r0/pushrules/device/{profileTag}/{kind}/{ruleId}/enabled](https://matrix.org/docs/spec/client_server/r0.6.0#put-matrix-client-r0-pushrules-scope-kind-ruleid-enabled)
+
+use ruma_api::ruma_api;
+
+use super::RuleKind;
+
+ruma_api! {
+    metadata {
+        description: "This endpoint allows clients to enable or disable the specified push rule.",
+        method: PUT,
+        name: "set_pushrule_enabled_device_scope",
+        path: "/_matrix/client/r0/pushrules/device/:profile_tag/:kind/:rule_id/enabled",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    request {
+        /// The profile tag of the device the rule belongs to.
+        #[ruma_api(path)]
+        pub profile_tag: String,
+        /// The kind of rule.
+        #[ruma_api(path)]
+        pub kind: RuleKind,
+        /// The identifier for the rule.
+        #[ruma_api(path)]
+        pub rule_id: String,
+        /// Whether the push rule is enabled or not.
+        pub enabled: bool,
+    }
+
+    response {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+    use crate::r0::push::RuleKind;
+
+    #[test]
+    fn disabled_rule_round_trip() {
+        let request = Request {
+            profile_tag: "mytag".to_string(),
+            kind: RuleKind::Override,
+            rule_id: ".m.rule.suppress_notices".to_string(),
+            enabled: false,
+        };
+
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+        assert_eq!(
+            http_request.uri().path(),
+            "/_matrix/client/r0/pushrules/device/mytag/override/.m.rule.suppress_notices/enabled"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_request.body()).unwrap(),
+            json!({ "enabled": false })
+        );
+
+        let request = Request::try_from(http_request).unwrap();
+        assert_eq!(request.profile_tag, "mytag");
+        assert_eq!(request.kind, RuleKind::Override);
+        assert_eq!(request.rule_id, ".m.rule.suppress_notices");
+        assert!(!request.enabled);
+    }
+}
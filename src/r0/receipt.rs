@@ -1,3 +1,69 @@
 //! Endpoints for event receipts.
 
 pub mod create_receipt;
+
+use ruma_identifiers::{EventId, RoomId};
+
+use self::create_receipt::ReceiptType;
+
+/// Creates a `create_receipt` request sending a read receipt for each of the given rooms and
+/// the events to acknowledge up to.
+///
+/// A receipt request only covers a single room, so this is a shortcut for sending one per room
+/// when catching up on many rooms at once, e.g. after a "mark all as read" action.
+pub fn mark_all_read(entries: &[(RoomId, EventId)]) -> Vec<create_receipt::Request> {
+    entries
+        .iter()
+        .map(|(room_id, event_id)| create_receipt::Request {
+            event_id: event_id.clone(),
+            receipt_type: ReceiptType::Read,
+            room_id: room_id.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_identifiers::{EventId, RoomId};
+
+    use super::mark_all_read;
+
+    #[test]
+    fn one_request_per_room() {
+        let entries = vec![
+            (
+                RoomId::try_from("!a:example.org").unwrap(),
+                EventId::try_from("$1:example.org").unwrap(),
+            ),
+            (
+                RoomId::try_from("!b:example.org").unwrap(),
+                EventId::try_from("$2:example.org").unwrap(),
+            ),
+            (
+                RoomId::try_from("!c:example.org").unwrap(),
+                EventId::try_from("$3:example.org").unwrap(),
+            ),
+        ];
+
+        let paths: Vec<_> = mark_all_read(&entries)
+            .into_iter()
+            .map(|request| {
+                http::Request::<Vec<u8>>::try_from(request)
+                    .unwrap()
+                    .uri()
+                    .path()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "/_matrix/client/r0/rooms/!a:example.org/receipt/m.read/$1:example.org",
+                "/_matrix/client/r0/rooms/!b:example.org/receipt/m.read/$2:example.org",
+                "/_matrix/client/r0/rooms/!c:example.org/receipt/m.read/$3:example.org",
+            ]
+        );
+    }
+}
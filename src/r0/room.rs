@@ -5,12 +5,52 @@ pub mod get_room_event;
 
 use serde::{Deserialize, Serialize};
 
-/// Whether or not a newly created room will be listed in the room directory.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+/// Whether or not a room will be listed in the room directory.
+// `#[non_exhaustive]` requires Rust 1.40.
+#[allow(clippy::manual_non_exhaustive)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Visibility {
     /// Indicates that the room will be shown in the published room list.
     Public,
     /// Indicates that the room will not be shown in the published room list.
     Private,
+    /// Additional variants may be added in the future and will not be considered breaking changes
+    /// to ruma-client-api.
+    #[doc(hidden)]
+    #[serde(skip)]
+    __Nonexhaustive,
+}
+
+impl Default for Visibility {
+    /// Rooms are private unless specified otherwise.
+    fn default() -> Self {
+        Visibility::Private
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::Visibility;
+
+    #[test]
+    fn default_visibility_is_private() {
+        assert_eq!(Visibility::default(), Visibility::Private);
+    }
+
+    #[test]
+    fn visibility_round_trip() {
+        for &(visibility, json) in &[
+            (Visibility::Public, "public"),
+            (Visibility::Private, "private"),
+        ] {
+            assert_eq!(to_json_value(visibility).unwrap(), json!(json));
+            assert_eq!(
+                from_json_value::<Visibility>(json!(json)).unwrap(),
+                visibility
+            );
+        }
+    }
 }
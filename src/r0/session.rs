@@ -4,3 +4,49 @@ pub mod get_login_types;
 pub mod login;
 pub mod logout;
 pub mod logout_all;
+
+use std::time::{Duration, SystemTime};
+
+use js_int::UInt;
+
+/// The time at which an access token that expires after `expires_in_ms` should be refreshed.
+///
+/// `received_at` is the time the response carrying `expires_in_ms` was received. The result is
+/// `margin` before the token expires, or `received_at` if the token expires within the margin.
+pub fn expiry_instant(
+    received_at: SystemTime,
+    expires_in_ms: UInt,
+    margin: Duration,
+) -> SystemTime {
+    let expires_in = Duration::from_millis(u64::from(expires_in_ms));
+    received_at + expires_in.checked_sub(margin).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use js_int::UInt;
+
+    use super::expiry_instant;
+
+    #[test]
+    fn refresh_before_expiry() {
+        let received_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+
+        assert_eq!(
+            expiry_instant(received_at, UInt::from(300_000u32), Duration::from_secs(60)),
+            received_at + Duration::from_secs(240)
+        );
+    }
+
+    #[test]
+    fn refresh_immediately_within_margin() {
+        let received_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+
+        assert_eq!(
+            expiry_instant(received_at, UInt::from(30_000u32), Duration::from_secs(60)),
+            received_at
+        );
+    }
+}
@@ -24,9 +24,54 @@ ruma_api! {
 }
 
 /// A supported login type in a homeserver
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LoginFlow {
     /// The login type.
     #[serde(rename = "type")]
     pub login_type: LoginType,
+    /// Whether this SSO flow is compatible with a homeserver delegating authentication to an
+    /// OpenID Connect provider.
+    ///
+    /// Clients supporting OpenID Connect should prefer its native login in that case.
+    #[serde(
+        default,
+        alias = "org.matrix.msc3824.delegated_oidc_compatibility",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub delegated_oidc_compatibility: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::Response;
+    use crate::r0::session::login::LoginType;
+
+    #[test]
+    fn deserialize_login_flows() {
+        let body = json!({
+            "flows": [
+                { "type": "m.login.sso", "delegated_oidc_compatibility": true },
+                { "type": "m.login.token" },
+                { "type": "org.example.login.custom" },
+            ]
+        });
+        let http_response = http::Response::builder()
+            .body(to_json_vec(&body).unwrap())
+            .unwrap();
+
+        let response = Response::try_from(http_response).unwrap();
+        assert_eq!(response.flows.len(), 3);
+        assert_eq!(response.flows[0].login_type, LoginType::Sso);
+        assert!(response.flows[0].delegated_oidc_compatibility);
+        assert_eq!(response.flows[1].login_type, LoginType::Token);
+        assert!(!response.flows[1].delegated_oidc_compatibility);
+        assert_eq!(
+            response.flows[2].login_type,
+            LoginType::Custom("org.example.login.custom".to_string())
+        );
+    }
 }
@@ -2,7 +2,9 @@
 
 use ruma_api::ruma_api;
 use ruma_identifiers::{DeviceId, UserId};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::unversioned::discover_homeserver::DiscoveryInfo;
 
 ruma_api! {
     metadata {
@@ -16,7 +18,11 @@ ruma_api! {
 
     request {
         /// The user's password.
-        pub password: String,
+        ///
+        /// Required for `LoginType::Password`. Application services log in with their access
+        /// token instead and leave this empty.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub password: Option<String>,
         /// When logging in using a third party identifier, the medium of the identifier.
         #[serde(skip_serializing_if = "Option::is_none")]
         pub medium: Option<Medium>,
@@ -31,6 +37,11 @@ ruma_api! {
         /// ID of the client device
         #[serde(skip_serializing_if = "Option::is_none")]
         pub device_id: Option<DeviceId>,
+        /// A display name to assign to the newly-created device.
+        ///
+        /// Ignored if `device_id` corresponds to a known device.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub initial_device_display_name: Option<String>,
     }
 
     response {
@@ -49,6 +60,10 @@ ruma_api! {
         /// Will be the same as the corresponging parameter in the request, if one was
         /// specified.
         pub device_id: String,
+        /// Information to configure the client with, overriding what was discovered with
+        /// `discover_homeserver`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub well_known: Option<DiscoveryInfo>,
     }
 }
 
@@ -61,9 +76,89 @@ pub enum Medium {
 }
 
 /// The authentication mechanism.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LoginType {
     /// A password is supplied to authenticate.
-    #[serde(rename = "m.login.password")]
     Password,
+    /// A login token previously obtained from the homeserver is supplied to authenticate, e.g.
+    /// at the end of a single sign-on flow.
+    Token,
+    /// An application service logs in as one of its users.
+    ///
+    /// The application service's access token is used to authenticate the request.
+    ApplicationService,
+    /// The user authenticates with a single sign-on provider of the homeserver.
+    Sso,
+    /// A login type that isn't known to ruma-client-api, such as an unstable one.
+    Custom(String),
+}
+
+impl LoginType {
+    /// The string used for this login type in JSON, e.g. `m.login.password`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            LoginType::Password => "m.login.password",
+            LoginType::Token => "m.login.token",
+            LoginType::ApplicationService => "m.login.application_service",
+            LoginType::Sso => "m.login.sso",
+            LoginType::Custom(login_type) => login_type,
+        }
+    }
+}
+
+impl Serialize for LoginType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for LoginType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let login_type = String::deserialize(deserializer)?;
+
+        Ok(match login_type.as_str() {
+            "m.login.password" => LoginType::Password,
+            "m.login.token" => LoginType::Token,
+            "m.login.application_service" => LoginType::ApplicationService,
+            "m.login.sso" => LoginType::Sso,
+            _ => LoginType::Custom(login_type),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{LoginType, Request};
+
+    #[test]
+    fn application_service_login_has_no_password() {
+        let request = Request {
+            password: None,
+            medium: None,
+            login_type: LoginType::ApplicationService,
+            user: "@bridge_alice:example.org".to_string(),
+            address: None,
+            device_id: None,
+            initial_device_display_name: None,
+        };
+
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_request.body()).unwrap(),
+            json!({
+                "type": "m.login.application_service",
+                "user": "@bridge_alice:example.org",
+            })
+        );
+    }
 }
@@ -5,3 +5,14 @@ pub mod create_state_event_for_key;
 pub mod get_state_events;
 pub mod get_state_events_for_empty_key;
 pub mod get_state_events_for_key;
+
+use ruma_events::{EventResult, InvalidEvent, TryFromRaw};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Converts the JSON content of a state event into a ruma-events content type.
+fn content_as<C: TryFromRaw>(content: &Value) -> Result<C, InvalidEvent> {
+    EventResult::deserialize(content)
+        .expect("deserializing an EventResult from a JSON value never fails")
+        .into_result()
+}
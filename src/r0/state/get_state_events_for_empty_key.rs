@@ -1,7 +1,7 @@
 //! [GET /_matrix/client/r0/rooms/{roomId}/state/{eventType}](https://matrix.org/docs/spec/client_server/r0.4.0.html#get-matrix-client-r0-rooms-roomid-state-eventtype)
 
 use ruma_api::ruma_api;
-use ruma_events::EventType;
+use ruma_events::{EventType, InvalidEvent, TryFromRaw};
 use ruma_identifiers::RoomId;
 use serde_json::Value;
 
@@ -30,3 +30,35 @@ ruma_api! {
         pub content: Value,
     }
 }
+
+impl Response {
+    /// Converts the content of the state event into the corresponding ruma-events content type,
+    /// e.g. `ruma_events::room::encryption::EncryptionEventContent` for `m.room.encryption`.
+    pub fn content_as<C: TryFromRaw>(&self) -> Result<C, InvalidEvent> {
+        super::content_as(&self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::UInt;
+    use ruma_events::{room::encryption::EncryptionEventContent, Algorithm};
+    use serde_json::json;
+
+    use super::Response;
+
+    #[test]
+    fn encryption_content() {
+        let response = Response {
+            content: json!({
+                "algorithm": "m.megolm.v1.aes-sha2",
+                "rotation_period_ms": 604_800_000,
+            }),
+        };
+
+        let content = response.content_as::<EncryptionEventContent>().unwrap();
+        assert_eq!(content.algorithm, Algorithm::MegolmV1AesSha2);
+        assert_eq!(content.rotation_period_ms, Some(UInt::from(604_800_000u32)));
+        assert_eq!(content.rotation_period_msgs, None);
+    }
+}
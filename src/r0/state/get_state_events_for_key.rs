@@ -1,7 +1,7 @@
 //! [GET /_matrix/client/r0/rooms/{roomId}/state/{eventType}/{stateKey}](https://matrix.org/docs/spec/client_server/r0.4.0.html#get-matrix-client-r0-rooms-roomid-state-eventtype-state-key)
 
 use ruma_api::ruma_api;
-use ruma_events::EventType;
+use ruma_events::{EventType, InvalidEvent, TryFromRaw};
 use ruma_identifiers::RoomId;
 use serde_json::Value;
 
@@ -33,3 +33,11 @@ ruma_api! {
         pub content: Value,
     }
 }
+
+impl Response {
+    /// Converts the content of the state event into the corresponding ruma-events content type,
+    /// e.g. `ruma_events::room::encryption::EncryptionEventContent` for `m.room.encryption`.
+    pub fn content_as<C: TryFromRaw>(&self) -> Result<C, InvalidEvent> {
+        super::content_as(&self.content)
+    }
+}
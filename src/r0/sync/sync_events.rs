@@ -13,10 +13,14 @@ use ruma_events::{
     stripped::StrippedState,
     EventResult,
 };
-use ruma_identifiers::RoomId;
+use ruma_identifiers::{RoomId, UserId};
 use serde::{Deserialize, Serialize};
+use url::form_urlencoded;
 
-use crate::r0::filter::FilterDefinition;
+use crate::r0::{
+    filter::{create_filter, FilterDefinition},
+    keys::{KeyAlgorithm, OneTimeKeyCounts},
+};
 
 ruma_api! {
     metadata {
@@ -60,6 +64,74 @@ ruma_api! {
         /// Updates to the presence status of other users.
         #[wrap_incoming]
         pub presence: Presence,
+        /// For each key algorithm, the number of unclaimed one-time keys currently held on the
+        /// server for this device.
+        #[serde(default, skip_serializing_if = "OneTimeKeyCounts::is_empty")]
+        pub device_one_time_keys_count: OneTimeKeyCounts,
+        /// The algorithms of the fallback keys uploaded for this device that haven't been used
+        /// yet.
+        ///
+        /// `None` if the homeserver doesn't support fallback keys.
+        #[serde(
+            alias = "org.matrix.msc2732.device_unused_fallback_key_types",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub device_unused_fallback_key_types: Option<Vec<KeyAlgorithm>>,
+    }
+}
+
+impl Response {
+    /// Whether a new fallback key of the given algorithm should be uploaded for this device.
+    ///
+    /// This is the case if the homeserver supports fallback keys and the last uploaded fallback
+    /// key of that algorithm has been used, or none was uploaded yet.
+    pub fn needs_fallback_key(&self, algorithm: KeyAlgorithm) -> bool {
+        needs_fallback_key(&self.device_unused_fallback_key_types, algorithm)
+    }
+}
+
+impl IncomingResponse {
+    /// Whether a new fallback key of the given algorithm should be uploaded for this device.
+    ///
+    /// This is the case if the homeserver supports fallback keys and the last uploaded fallback
+    /// key of that algorithm has been used, or none was uploaded yet.
+    pub fn needs_fallback_key(&self, algorithm: KeyAlgorithm) -> bool {
+        needs_fallback_key(&self.device_unused_fallback_key_types, algorithm)
+    }
+}
+
+fn needs_fallback_key(unused_types: &Option<Vec<KeyAlgorithm>>, algorithm: KeyAlgorithm) -> bool {
+    match unused_types {
+        Some(unused_types) => !unused_types.contains(&algorithm),
+        None => false,
+    }
+}
+
+/// Threads the `next_batch` token of each sync response into the `since` parameter of the
+/// following sync request.
+///
+/// This type doesn't send any requests itself, so it can be used with any HTTP client.
+#[derive(Clone, Debug)]
+pub struct SyncLoop {
+    request: Request,
+}
+
+impl SyncLoop {
+    /// Creates a new `SyncLoop` that sends requests like `request`, starting at its `since`
+    /// parameter.
+    pub fn new(request: Request) -> Self {
+        Self { request }
+    }
+
+    /// The request to start the sync loop with.
+    pub fn first_request(&self) -> Request {
+        self.request.clone()
+    }
+
+    /// The request to send after having received `response`.
+    pub fn next_request(&mut self, response: &IncomingResponse) -> Request {
+        self.request.since = Some(response.next_batch.clone());
+        self.request.clone()
     }
 }
 
@@ -93,6 +165,47 @@ pub enum Filter {
     FilterId(String),
 }
 
+impl Filter {
+    /// The length of the URL-encoded `filter` query parameter above which a filter definition
+    /// should be uploaded instead of being sent inline.
+    ///
+    /// Homeservers and proxies in front of them may reject URLs exceeding a few kilobytes.
+    pub const MAX_ENCODED_LEN: usize = 2048;
+
+    /// The length of this filter once URL-encoded into the query string of a sync request,
+    /// including the `filter=` prefix.
+    pub fn encoded_len(&self) -> usize {
+        let value = match self {
+            Filter::FilterDefinition(filter_def) => {
+                serde_json::to_string(filter_def).expect("filter definitions serialize to JSON")
+            }
+            Filter::FilterId(filter_id) => filter_id.clone(),
+        };
+
+        "filter=".len()
+            + form_urlencoded::byte_serialize(value.as_bytes())
+                .map(str::len)
+                .sum::<usize>()
+    }
+
+    /// Creates a `create_filter` request uploading this filter definition for the given user, if
+    /// it is too large to be sent inline.
+    ///
+    /// Send the request and sync with `Filter::FilterId` and the returned ID instead. Returns
+    /// `None` for filter IDs and definitions that are small enough.
+    pub fn upload_request_if_too_large(&self, user_id: &UserId) -> Option<create_filter::Request> {
+        match self {
+            Filter::FilterDefinition(filter_def) if self.encoded_len() > Self::MAX_ENCODED_LEN => {
+                Some(create_filter::Request {
+                    filter: filter_def.clone(),
+                    user_id: user_id.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Serialization and deserialization logic for filter definitions.
 mod filter_def_serde {
     use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serializer};
@@ -150,6 +263,17 @@ pub struct LeftRoom {
 pub struct JoinedRoom {
     /// Counts of unread notifications for this room.
     pub unread_notifications: UnreadNotificationsCount,
+    /// Counts of unread notifications for the threads in this room, keyed by the ID of the
+    /// thread's root event.
+    ///
+    /// This uses the unstable prefix of [MSC3773](https://github.com/matrix-org/matrix-doc/pull/3773).
+    #[cfg(feature = "unstable-msc3773")]
+    #[serde(
+        default,
+        rename = "org.matrix.msc3773.unread_thread_notifications",
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub unread_thread_notifications: HashMap<ruma_identifiers::EventId, UnreadNotificationsCount>,
     /// The timeline of messages and state changes in the room.
     #[wrap_incoming]
     pub timeline: Timeline,
@@ -178,6 +302,19 @@ pub struct UnreadNotificationsCount {
     pub notification_count: Option<UInt>,
 }
 
+impl UnreadNotificationsCount {
+    /// Whether the highlight count or the notification count increased since `previous`.
+    ///
+    /// Missing counts are treated as zero. Can be used to decide whether to notify the user
+    /// again after a sync.
+    pub fn increased_since(&self, previous: &UnreadNotificationsCount) -> bool {
+        let count = |count: Option<UInt>| count.unwrap_or_else(|| UInt::from(0u32));
+
+        count(self.highlight_count) > count(previous.highlight_count)
+            || count(self.notification_count) > count(previous.notification_count)
+    }
+}
+
 /// Events in the room.
 #[derive(Clone, Debug, Serialize, Outgoing)]
 pub struct Timeline {
@@ -239,3 +376,144 @@ pub struct Presence {
     #[wrap_incoming(PresenceEvent with EventResult)]
     pub events: Vec<PresenceEvent>,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use js_int::UInt;
+    use ruma_identifiers::UserId;
+    use serde_json::{json, to_vec as to_json_vec, Value as JsonValue};
+
+    use super::{Filter, IncomingResponse, Request, SyncLoop, UnreadNotificationsCount};
+    use crate::r0::{filter::FilterDefinition, keys::KeyAlgorithm};
+
+    fn response(fields: JsonValue) -> IncomingResponse {
+        let mut body = json!({
+            "next_batch": "s1",
+            "rooms": { "leave": {}, "join": {}, "invite": {} },
+            "presence": { "events": [] },
+        });
+        for (key, value) in fields.as_object().unwrap() {
+            body[key] = value.clone();
+        }
+
+        let http_response = http::Response::builder()
+            .body(to_json_vec(&body).unwrap())
+            .unwrap();
+        IncomingResponse::try_from(http_response).unwrap()
+    }
+
+    #[test]
+    fn notification_count_increased() {
+        let previous = UnreadNotificationsCount {
+            highlight_count: None,
+            notification_count: Some(UInt::from(2u32)),
+        };
+        let current = UnreadNotificationsCount {
+            highlight_count: None,
+            notification_count: Some(UInt::from(5u32)),
+        };
+
+        assert!(current.increased_since(&previous));
+        assert!(!previous.increased_since(&current));
+    }
+
+    #[test]
+    fn sync_loop_continues_from_next_batch() {
+        let mut sync_loop = SyncLoop::new(Request {
+            filter: None,
+            since: None,
+            full_state: None,
+            set_presence: None,
+            timeout: Some(UInt::from(30_000u32)),
+        });
+        assert_eq!(sync_loop.first_request().since, None);
+
+        let request = sync_loop.next_request(&response(json!({ "next_batch": "s1" })));
+        assert_eq!(request.since, Some("s1".to_string()));
+        assert_eq!(request.timeout, Some(UInt::from(30_000u32)));
+
+        let request = sync_loop.next_request(&response(json!({ "next_batch": "s2" })));
+        assert_eq!(request.since, Some("s2".to_string()));
+    }
+
+    #[test]
+    fn needs_fallback_key() {
+        let unused_key = response(json!({
+            "device_unused_fallback_key_types": ["signed_curve25519"],
+        }));
+        assert!(!unused_key.needs_fallback_key(KeyAlgorithm::SignedCurve25519));
+        assert!(unused_key.needs_fallback_key(KeyAlgorithm::Curve25519));
+
+        let used_key = response(json!({ "device_unused_fallback_key_types": [] }));
+        assert!(used_key.needs_fallback_key(KeyAlgorithm::SignedCurve25519));
+    }
+
+    #[test]
+    fn fallback_keys_unsupported() {
+        assert!(!response(json!({})).needs_fallback_key(KeyAlgorithm::SignedCurve25519));
+    }
+
+    #[test]
+    fn upload_large_filter() {
+        let user_id = UserId::try_from("@alice:example.org").unwrap();
+        let mut filter_def = FilterDefinition::ignore_all();
+        filter_def.event_fields = Some(
+            (0..200)
+                .map(|index| format!("content.field_{}", index))
+                .collect(),
+        );
+        let filter = Filter::FilterDefinition(filter_def);
+        assert!(filter.encoded_len() > Filter::MAX_ENCODED_LEN);
+
+        let request = filter.upload_request_if_too_large(&user_id).unwrap();
+        assert_eq!(request.user_id, user_id);
+        assert_eq!(request.filter.event_fields.unwrap().len(), 200);
+    }
+
+    #[test]
+    fn keep_small_filter_inline() {
+        let user_id = UserId::try_from("@alice:example.org").unwrap();
+
+        let filter = Filter::FilterDefinition(FilterDefinition::ignore_all());
+        assert!(filter.upload_request_if_too_large(&user_id).is_none());
+
+        let filter = Filter::FilterId("filter".to_string());
+        assert!(filter.upload_request_if_too_large(&user_id).is_none());
+    }
+
+    #[cfg(feature = "unstable-msc3773")]
+    #[test]
+    fn unread_thread_notifications() {
+        use ruma_identifiers::{EventId, RoomId};
+
+        let response = response(json!({
+            "rooms": {
+                "leave": {},
+                "join": {
+                    "!room:example.org": {
+                        "unread_notifications": { "notification_count": 3 },
+                        "org.matrix.msc3773.unread_thread_notifications": {
+                            "$thread:example.org": {
+                                "highlight_count": 1,
+                                "notification_count": 2,
+                            },
+                        },
+                        "timeline": { "limited": false, "prev_batch": "p1", "events": [] },
+                        "state": { "events": [] },
+                        "account_data": { "events": [] },
+                        "ephemeral": { "events": [] },
+                    },
+                },
+                "invite": {},
+            },
+        }));
+
+        let room = &response.rooms.join[&RoomId::try_from("!room:example.org").unwrap()];
+        let thread =
+            &room.unread_thread_notifications[&EventId::try_from("$thread:example.org").unwrap()];
+        assert_eq!(thread.highlight_count, Some(UInt::from(1u32)));
+        assert_eq!(thread.notification_count, Some(UInt::from(2u32)));
+    }
+}
@@ -3,3 +3,90 @@
 pub mod create_tag;
 pub mod delete_tag;
 pub mod get_tags;
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use ruma_events::tag::TagInfo;
+use ruma_identifiers::{RoomId, UserId};
+
+/// Creates the requests deleting `tag` from a room and evenly re-spacing the orders of the
+/// room's remaining ordered tags between 0 and 1.
+///
+/// `tags` are the current tags of the room, e.g. from a `get_tags` response. A `create_tag`
+/// request is only created for tags whose order changes. Tags without an order are left as is.
+pub fn delete_and_rebalance(
+    user_id: &UserId,
+    room_id: &RoomId,
+    tags: &HashMap<String, TagInfo>,
+    tag: &str,
+) -> (delete_tag::Request, Vec<create_tag::Request>) {
+    let delete_request = delete_tag::Request {
+        room_id: room_id.clone(),
+        tag: tag.to_string(),
+        user_id: user_id.clone(),
+    };
+
+    let mut remaining: Vec<(&String, f64)> = tags
+        .iter()
+        .filter(|(name, _)| name.as_str() != tag)
+        .filter_map(|(name, info)| info.order.map(|order| (name, order)))
+        .collect();
+    remaining.sort_by(|(a_name, a_order), (b_name, b_order)| {
+        a_order
+            .partial_cmp(b_order)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a_name.cmp(b_name))
+    });
+
+    let step = 1.0 / (remaining.len() + 1) as f64;
+    let create_requests = remaining
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, order))| (name, order, step * (index + 1) as f64))
+        .filter(|(_, order, new_order)| order != new_order)
+        .map(|(name, _, new_order)| create_tag::Request {
+            room_id: room_id.clone(),
+            tag: name.clone(),
+            tag_info: TagInfo {
+                order: Some(new_order),
+            },
+            user_id: user_id.clone(),
+        })
+        .collect();
+
+    (delete_request, create_requests)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, convert::TryFrom};
+
+    use ruma_events::tag::TagInfo;
+    use ruma_identifiers::{RoomId, UserId};
+
+    use super::delete_and_rebalance;
+
+    #[test]
+    fn delete_middle_tag() {
+        let user_id = UserId::try_from("@alice:example.org").unwrap();
+        let room_id = RoomId::try_from("!room:example.org").unwrap();
+        let mut tags = HashMap::new();
+        for &(name, order) in &[("a", 0.2), ("b", 0.5), ("c", 0.8)] {
+            tags.insert(name.to_string(), TagInfo { order: Some(order) });
+        }
+
+        let (delete_request, create_requests) =
+            delete_and_rebalance(&user_id, &room_id, &tags, "b");
+        assert_eq!(delete_request.tag, "b");
+
+        let orders: Vec<_> = create_requests
+            .iter()
+            .map(|request| (request.tag.as_str(), request.tag_info.order.unwrap()))
+            .collect();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].0, "a");
+        assert!((orders[0].1 - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(orders[1].0, "c");
+        assert!((orders[1].1 - 2.0 / 3.0).abs() < 1e-9);
+    }
+}
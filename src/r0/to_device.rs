@@ -0,0 +1,63 @@
+//! Endpoints for client devices to exchange information not persisted in room DAGs.
+
+pub mod send_event_to_device;
+
+use std::{
+    convert::Infallible,
+    fmt::{Display, Error as FmtError, Formatter},
+    str::FromStr,
+};
+
+use ruma_identifiers::DeviceId;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The recipient devices of a to-device event for a user.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum DeviceIdOrAllDevices {
+    /// A single device of the user.
+    DeviceId(DeviceId),
+    /// All devices of the user.
+    AllDevices,
+}
+
+impl Display for DeviceIdOrAllDevices {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            DeviceIdOrAllDevices::DeviceId(device_id) => write!(f, "{}", device_id),
+            DeviceIdOrAllDevices::AllDevices => write!(f, "*"),
+        }
+    }
+}
+
+impl FromStr for DeviceIdOrAllDevices {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "*" => DeviceIdOrAllDevices::AllDevices,
+            _ => DeviceIdOrAllDevices::DeviceId(s.to_string()),
+        })
+    }
+}
+
+impl Serialize for DeviceIdOrAllDevices {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for DeviceIdOrAllDevices {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Ok(match value.parse() {
+            Ok(recipient) => recipient,
+            Err(never) => match never {},
+        })
+    }
+}
@@ -0,0 +1,130 @@
+//! [PUT /_matrix/client/r0/sendToDevice/{eventType}/{txnId}](https://matrix.org/docs/spec/client_server/r0.6.0#put-matrix-client-r0-sendtodevice-eventtype-txnid)
+
+use std::collections::HashMap;
+
+use ruma_api::ruma_api;
+use ruma_events::{Event, EventType};
+use ruma_identifiers::UserId;
+use serde_json::Value;
+
+use super::DeviceIdOrAllDevices;
+
+ruma_api! {
+    metadata {
+        description: "Send an event to a device or devices.",
+        method: PUT,
+        name: "send_event_to_device",
+        path: "/_matrix/client/r0/sendToDevice/:event_type/:txn_id",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    request {
+        /// The type of event to send.
+        #[ruma_api(path)]
+        pub event_type: EventType,
+        /// The transaction ID for this event.
+        ///
+        /// Clients should generate an ID unique across requests with the same access token; it
+        /// will be used by the server to ensure idempotency of requests.
+        #[ruma_api(path)]
+        pub txn_id: String,
+        /// The content of the events to send, for each of the recipient devices of each user.
+        pub messages: HashMap<UserId, HashMap<DeviceIdOrAllDevices, Value>>,
+    }
+
+    response {}
+}
+
+impl Request {
+    /// Creates a request sending the content of `event` to each of the given devices.
+    ///
+    /// `event` can be any event type of ruma-events, e.g. `ruma_events::room_key::RoomKeyEvent`
+    /// for `m.room_key`. The event type of the request is taken from the event, so it always
+    /// matches the content.
+    pub fn with_event<E, I>(
+        txn_id: impl Into<String>,
+        recipients: I,
+        event: &E,
+    ) -> Result<Self, serde_json::Error>
+    where
+        E: Event,
+        I: IntoIterator<Item = (UserId, DeviceIdOrAllDevices)>,
+    {
+        let content = serde_json::to_value(event.content())?;
+
+        let mut messages: HashMap<UserId, HashMap<DeviceIdOrAllDevices, Value>> = HashMap::new();
+        for (user_id, device) in recipients {
+            messages
+                .entry(user_id)
+                .or_default()
+                .insert(device, content.clone());
+        }
+
+        Ok(Self {
+            event_type: event.event_type(),
+            txn_id: txn_id.into(),
+            messages,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_events::{
+        room_key::{RoomKeyEvent, RoomKeyEventContent},
+        Algorithm,
+    };
+    use ruma_identifiers::{RoomId, UserId};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+    use crate::r0::to_device::DeviceIdOrAllDevices;
+
+    #[test]
+    fn room_key_event() {
+        let event = RoomKeyEvent {
+            content: RoomKeyEventContent {
+                algorithm: Algorithm::MegolmV1AesSha2,
+                room_id: RoomId::try_from("!room:example.org").unwrap(),
+                session_id: "session_id".to_string(),
+                session_key: "session_key".to_string(),
+            },
+        };
+        let recipients = vec![
+            (
+                UserId::try_from("@alice:example.org").unwrap(),
+                DeviceIdOrAllDevices::DeviceId("PHONE".to_string()),
+            ),
+            (
+                UserId::try_from("@bob:example.org").unwrap(),
+                DeviceIdOrAllDevices::AllDevices,
+            ),
+        ];
+
+        let request = Request::with_event("txn1", recipients, &event).unwrap();
+        let http_request = http::Request::<Vec<u8>>::try_from(request).unwrap();
+
+        assert_eq!(
+            http_request.uri().path(),
+            "/_matrix/client/r0/sendToDevice/m.room_key/txn1"
+        );
+        let content = json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "room_id": "!room:example.org",
+            "session_id": "session_id",
+            "session_key": "session_key",
+        });
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_request.body()).unwrap(),
+            json!({
+                "messages": {
+                    "@alice:example.org": { "PHONE": content },
+                    "@bob:example.org": { "*": content },
+                },
+            })
+        );
+    }
+}
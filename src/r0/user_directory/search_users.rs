@@ -1,10 +1,13 @@
 //! [POST /_matrix/client/r0/user_directory/search](https://matrix.org/docs/spec/client_server/r0.6.0#post-matrix-client-r0-user-directory-search)
 
+use http::header::{HeaderMap, HeaderValue, ACCEPT_LANGUAGE};
 use js_int::UInt;
 use ruma_api::ruma_api;
 use ruma_identifiers::UserId;
 use serde::{Deserialize, Serialize};
 
+use crate::extra_headers::WithExtraHeaders;
+
 ruma_api! {
     metadata {
         description: "Performs a search for users on the homeserver.",
@@ -33,6 +36,20 @@ ruma_api! {
     }
 }
 
+impl Request {
+    /// Converts this request into an `http::Request` with the given `Accept-Language` header.
+    ///
+    /// Homeservers may use the header to return localized results.
+    pub fn into_http_request_with_language(
+        self,
+        accept_language: HeaderValue,
+    ) -> Result<http::Request<Vec<u8>>, ruma_api::Error> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT_LANGUAGE, accept_language);
+        self.into_http_request_with_headers(headers)
+    }
+}
+
 /// User data as result of a search.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct User {
@@ -45,3 +62,27 @@ pub struct User {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar_url: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use http::header::{HeaderValue, ACCEPT_LANGUAGE};
+
+    use super::Request;
+
+    #[test]
+    fn accept_language_header() {
+        let request = Request {
+            search_term: "alice".to_string(),
+            limit: None,
+        };
+
+        let http_request = request
+            .into_http_request_with_language(HeaderValue::from_static("de-DE"))
+            .unwrap();
+        assert_eq!(http_request.headers()[ACCEPT_LANGUAGE], "de-DE");
+        assert_eq!(
+            http_request.uri().path(),
+            "/_matrix/client/r0/user_directory/search"
+        );
+    }
+}
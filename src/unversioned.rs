@@ -1,4 +1,5 @@
 //! Endpoints that cannot change with new versions of the Matrix specification.
 
 pub mod discover_homeserver;
+pub mod get_support;
 pub mod get_supported_versions;
@@ -18,6 +18,21 @@ pub struct IdentityServerInfo {
     pub base_url: Url,
 }
 
+/// Information about the homeserver and identity server to connect to.
+///
+/// Returned by some endpoints besides `discover_homeserver` to let clients update their
+/// configuration.
+#[derive(Clone, Debug, Deserialize, Hash, PartialEq, PartialOrd, Serialize)]
+pub struct DiscoveryInfo {
+    /// Information about the homeserver to connect to.
+    #[serde(rename = "m.homeserver")]
+    pub homeserver: HomeserverInfo,
+
+    /// Information about the identity server to connect to.
+    #[serde(rename = "m.identity_server", skip_serializing_if = "Option::is_none")]
+    pub identity_server: Option<IdentityServerInfo>,
+}
+
 ruma_api! {
     metadata {
         description: "Get discovery information about the domain.",
@@ -0,0 +1,100 @@
+//! [GET /.well-known/matrix/support](https://github.com/matrix-org/matrix-doc/pull/1929)
+
+use ruma_api::ruma_api;
+use ruma_identifiers::UserId;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// A way to contact the administrators of the homeserver.
+///
+/// At least one of `matrix_id` and `email_address` is set.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Contact {
+    /// The Matrix user ID of the contact.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matrix_id: Option<UserId>,
+
+    /// The email address of the contact.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_address: Option<String>,
+
+    /// The role of the contact, e.g. `m.role.admin` or `m.role.security`.
+    pub role: String,
+}
+
+ruma_api! {
+    metadata {
+        description: "Get contact information for the administrators of the domain.",
+        method: GET,
+        name: "get_support",
+        path: "/.well-known/matrix/support",
+        rate_limited: false,
+        requires_authentication: false,
+    }
+
+    request {}
+
+    response {
+        /// Ways to contact the administrators of the homeserver.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub contacts: Vec<Contact>,
+
+        /// A web page with information on how to get support for the homeserver.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub support_page: Option<Url>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_identifiers::UserId;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{Contact, Response};
+
+    #[test]
+    fn deserialize_support() {
+        let body = json!({
+            "contacts": [
+                { "matrix_id": "@admin:example.org", "role": "m.role.admin" },
+                { "email_address": "security@example.org", "role": "m.role.security" },
+            ],
+            "support_page": "https://example.org/support",
+        });
+        let http_response = http::Response::builder()
+            .body(to_json_vec(&body).unwrap())
+            .unwrap();
+
+        let response = Response::try_from(http_response).unwrap();
+        assert_eq!(
+            response.contacts,
+            vec![
+                Contact {
+                    matrix_id: Some(UserId::try_from("@admin:example.org").unwrap()),
+                    email_address: None,
+                    role: "m.role.admin".to_string(),
+                },
+                Contact {
+                    matrix_id: None,
+                    email_address: Some("security@example.org".to_string()),
+                    role: "m.role.security".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            response.support_page.unwrap().as_str(),
+            "https://example.org/support"
+        );
+    }
+
+    #[test]
+    fn deserialize_empty_support() {
+        let http_response = http::Response::builder().body(b"{}".to_vec()).unwrap();
+
+        let response = Response::try_from(http_response).unwrap();
+        assert!(response.contacts.is_empty());
+        assert!(response.support_page.is_none());
+    }
+}